@@ -0,0 +1,383 @@
+//! Local fake Alpha Vantage HTTP server for end-to-end tests
+//!
+//! Serves `/query` from fixture files, and simulates the upstream failure modes
+//! with Alpha Vantage's exact body shapes: per-minute and daily quota exhaustion,
+//! premium-only endpoints, invalid symbols and missing API keys.
+//!
+//! Fixtures are looked up as `<dir>/<FUNCTION>/<SYMBOL>.json` (or `.csv` when the
+//! request has `datatype=csv`), falling back to `<dir>/<FUNCTION>.json` for
+//! functions without a symbol. `REALTIME_BULK_QUOTES` requests are assembled
+//! from one quote object per symbol, omitting symbols without a fixture like the
+//! real API does.
+//!
+//! ```no_run
+//! # async fn example() -> std::io::Result<()> {
+//! let server = alphav_fake::FakeServer::builder()
+//!     .fixtures_dir("alphav-fake/fixtures")
+//!     .per_minute_limit(5)
+//!     .start()
+//!     .await?;
+//!
+//! // Point the client at the fake server
+//! let base_url = server.base_url();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// Upstream message bodies, verbatim
+pub mod messages {
+    /// `Information` returned once the daily quota is exhausted
+    pub const DAILY_LIMIT: &str = "Thank you for using Alpha Vantage! Our standard API rate limit is 25 requests per day. Please subscribe to any of the premium plans at https://www.alphavantage.co/premium/ to instantly remove all daily rate limits.";
+
+    /// `Note` returned when the per-minute call frequency is exceeded
+    pub const PER_MINUTE_LIMIT: &str = "Thank you for using Alpha Vantage! Our standard API call frequency is 5 calls per minute and 500 calls per day. Please visit https://www.alphavantage.co/premium/ if you would like to target a higher API call frequency.";
+
+    /// `Information` returned for premium-only endpoints on a free key
+    pub const PREMIUM_ONLY: &str = "Thank you for using Alpha Vantage! This is a premium endpoint. You may subscribe to any of the premium plans at https://www.alphavantage.co/premium/ to instantly unlock all premium endpoints";
+
+    /// `Error Message` returned when the API key is missing
+    pub const MISSING_API_KEY: &str = "the parameter apikey is invalid or missing. Please claim your free API key on (https://www.alphavantage.co/support/#api-key). It should take less than 20 seconds.";
+
+    /// `Error Message` returned for invalid symbols or parameters
+    pub fn invalid_call(function: &str) -> String {
+        format!(
+            "Invalid API call. Please retry or visit the documentation (https://www.alphavantage.co/documentation/) for {function}."
+        )
+    }
+}
+
+/// Functions that require a premium key by default
+pub const PREMIUM_FUNCTIONS: &[&str] = &["REALTIME_BULK_QUOTES"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Csv => "text/csv",
+        }
+    }
+}
+
+type FixtureKey = (String, Option<String>, Format);
+
+#[derive(Debug, Default)]
+struct Usage {
+    minute: VecDeque<Instant>,
+    day_started: Option<Instant>,
+    day_count: u32,
+}
+
+#[derive(Debug)]
+struct State {
+    fixtures_dir: Option<PathBuf>,
+    fixtures: HashMap<FixtureKey, String>,
+    per_minute_limit: Option<u32>,
+    daily_limit: Option<u32>,
+    premium_functions: HashSet<String>,
+    premium_keys: HashSet<String>,
+    usage: Mutex<HashMap<String, Usage>>,
+    requests: Mutex<Vec<String>>,
+}
+
+/// Builder for [`FakeServer`]
+#[derive(Debug)]
+pub struct Builder {
+    addr: SocketAddr,
+    state: State,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            state: State {
+                fixtures_dir: None,
+                fixtures: HashMap::new(),
+                per_minute_limit: None,
+                daily_limit: None,
+                premium_functions: PREMIUM_FUNCTIONS.iter().map(|f| f.to_string()).collect(),
+                premium_keys: HashSet::new(),
+                usage: Mutex::default(),
+                requests: Mutex::default(),
+            },
+        }
+    }
+}
+
+impl Builder {
+    /// Address to listen on. Defaults to `127.0.0.1` on an ephemeral port.
+    pub fn bind(mut self, addr: SocketAddr) -> Self {
+        self.addr = addr;
+        self
+    }
+
+    /// Directory to read fixture files from.
+    pub fn fixtures_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.state.fixtures_dir = Some(dir.into());
+        self
+    }
+
+    /// Register an in-memory JSON fixture. Takes precedence over fixture files.
+    pub fn fixture(mut self, function: &str, symbol: Option<&str>, body: impl Into<String>) -> Self {
+        let key = (function.to_string(), symbol.map(str::to_string), Format::Json);
+        self.state.fixtures.insert(key, body.into());
+        self
+    }
+
+    /// Register an in-memory CSV fixture, served for `datatype=csv` requests.
+    pub fn csv_fixture(mut self, function: &str, symbol: Option<&str>, body: impl Into<String>) -> Self {
+        let key = (function.to_string(), symbol.map(str::to_string), Format::Csv);
+        self.state.fixtures.insert(key, body.into());
+        self
+    }
+
+    /// Allow at most `limit` requests per API key in any 60 second window.
+    pub fn per_minute_limit(mut self, limit: u32) -> Self {
+        self.state.per_minute_limit = Some(limit);
+        self
+    }
+
+    /// Allow at most `limit` requests per API key per day.
+    pub fn daily_limit(mut self, limit: u32) -> Self {
+        self.state.daily_limit = Some(limit);
+        self
+    }
+
+    /// Mark `function` as premium-only.
+    pub fn premium_function(mut self, function: impl Into<String>) -> Self {
+        self.state.premium_functions.insert(function.into());
+        self
+    }
+
+    /// Treat `key` as a premium key, exempt from premium-only errors.
+    pub fn premium_key(mut self, key: impl Into<String>) -> Self {
+        self.state.premium_keys.insert(key.into());
+        self
+    }
+
+    /// Bind the listener and start serving in a background task.
+    pub async fn start(self) -> std::io::Result<FakeServer> {
+        let listener = TcpListener::bind(self.addr).await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(self.state);
+
+        let accept_state = state.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = accept_state.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(move |req| {
+                        let state = state.clone();
+                        async move { Ok::<_, Infallible>(handle(&state, req)) }
+                    });
+                    // Connection errors only affect the one client
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+
+        Ok(FakeServer { addr, state, task })
+    }
+}
+
+/// A running fake Alpha Vantage server. Stops when dropped.
+#[derive(Debug)]
+pub struct FakeServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+    task: JoinHandle<()>,
+}
+
+impl FakeServer {
+    /// Start configuring a server.
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// The address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Base URL to configure on the client, e.g. `http://127.0.0.1:54321`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Query strings of every request received, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.state.requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn handle(state: &State, req: hyper::Request<hyper::body::Incoming>) -> hyper::Response<Full<Bytes>> {
+    if req.uri().path() != "/query" {
+        return response(404, "text/plain", "Not Found".to_string());
+    }
+    let query = req.uri().query().unwrap_or_default();
+    state
+        .requests
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(query.to_string());
+
+    let (content_type, body) = respond(state, query);
+    response(200, content_type, body)
+}
+
+fn response(status: u16, content_type: &str, body: String) -> hyper::Response<Full<Bytes>> {
+    hyper::Response::builder()
+        .status(status)
+        .header("content-type", content_type)
+        .body(Full::new(Bytes::from(body)))
+        .expect("static response parts are valid")
+}
+
+/// Compute the response body for a `/query` request. Like the real API, every
+/// outcome (including errors) is a `200` with a JSON message.
+fn respond(state: &State, query: &str) -> (&'static str, String) {
+    let params: HashMap<String, String> = form_urlencoded::parse(query.as_bytes()).into_owned().collect();
+    let message = |key: &str, text: &str| ("application/json", json!({ key: text }).to_string());
+
+    let Some(api_key) = params.get("apikey").filter(|k| !k.is_empty()) else {
+        return message("Error Message", messages::MISSING_API_KEY);
+    };
+    let function = params.get("function").map(String::as_str).unwrap_or_default();
+
+    if let Some(limited) = consume_quota(state, api_key) {
+        return limited;
+    }
+    if state.premium_functions.contains(function) && !state.premium_keys.contains(api_key) {
+        return message("Information", messages::PREMIUM_ONLY);
+    }
+
+    let format = match params.get("datatype").map(String::as_str) {
+        Some("csv") => Format::Csv,
+        _ => Format::Json,
+    };
+    let symbol = params.get("symbol").map(String::as_str);
+
+    // Bulk quotes are assembled from per-symbol fixtures, even for one symbol
+    if function == "REALTIME_BULK_QUOTES"
+        && format == Format::Json
+        && let Some(symbols) = symbol
+    {
+        return ("application/json", bulk_quotes(state, symbols));
+    }
+    if let Some(body) = fixture(state, function, symbol, format) {
+        return (format.content_type(), body);
+    }
+    message("Error Message", &messages::invalid_call(function))
+}
+
+fn consume_quota(state: &State, api_key: &str) -> Option<(&'static str, String)> {
+    let now = Instant::now();
+    let mut usage = state.usage.lock().unwrap_or_else(|e| e.into_inner());
+    let usage = usage.entry(api_key.to_string()).or_default();
+
+    let day_started = *usage.day_started.get_or_insert(now);
+    if now.duration_since(day_started) >= Duration::from_secs(24 * 60 * 60) {
+        usage.day_started = Some(now);
+        usage.day_count = 0;
+    }
+    while usage
+        .minute
+        .front()
+        .is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60))
+    {
+        usage.minute.pop_front();
+    }
+
+    if state.daily_limit.is_some_and(|limit| usage.day_count >= limit) {
+        return Some((
+            "application/json",
+            json!({ "Information": messages::DAILY_LIMIT }).to_string(),
+        ));
+    }
+    if state
+        .per_minute_limit
+        .is_some_and(|limit| usage.minute.len() >= limit as usize)
+    {
+        return Some((
+            "application/json",
+            json!({ "Note": messages::PER_MINUTE_LIMIT }).to_string(),
+        ));
+    }
+
+    usage.day_count += 1;
+    usage.minute.push_back(now);
+    None
+}
+
+fn fixture(state: &State, function: &str, symbol: Option<&str>, format: Format) -> Option<String> {
+    let key = (function.to_string(), symbol.map(str::to_string), format);
+    if let Some(body) = state.fixtures.get(&key) {
+        return Some(body.clone());
+    }
+    let dir = state.fixtures_dir.as_deref()?;
+    let path = match symbol {
+        Some(symbol) => fixture_path(dir, function, symbol, format)?,
+        None => safe(function).then(|| dir.join(format!("{function}.{}", format.extension())))?,
+    };
+    std::fs::read_to_string(path).ok()
+}
+
+fn fixture_path(dir: &Path, function: &str, symbol: &str, format: Format) -> Option<PathBuf> {
+    (safe(function) && safe(symbol)).then(|| dir.join(function).join(format!("{symbol}.{}", format.extension())))
+}
+
+/// Reject path components in user-supplied names so requests cannot escape the fixture directory.
+fn safe(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "._-^".contains(c)) && name != ".."
+}
+
+fn bulk_quotes(state: &State, symbols: &str) -> String {
+    let data: Vec<Value> = symbols
+        .split(',')
+        .filter_map(|symbol| fixture(state, "REALTIME_BULK_QUOTES", Some(symbol), Format::Json))
+        .filter_map(|body| serde_json::from_str(&body).ok())
+        .collect();
+    json!({
+        "endpoint": "Realtime Bulk Quotes",
+        "message": "",
+        "data": data,
+    })
+    .to_string()
+}
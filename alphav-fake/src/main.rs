@@ -0,0 +1,62 @@
+//! Run a fake Alpha Vantage server from the command line
+//!
+//! ```sh
+//! cargo run -p alphav-fake -- --port 8080 --per-minute 5 --daily 25
+//! ```
+
+use std::net::SocketAddr;
+
+const USAGE: &str = "Usage: alphav-fake [--port PORT] [--fixtures DIR] [--per-minute N] [--daily N] \
+                     [--premium FUNCTION]... [--premium-key KEY]...";
+
+fn parse_args() -> Result<alphav_fake::Builder, String> {
+    let mut builder = alphav_fake::FakeServer::builder().fixtures_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"));
+    let mut args = std::env::args().skip(1);
+
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            return Err(USAGE.to_string());
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {flag}\n{USAGE}"))?;
+        let number = |v: &str| v.parse::<u32>().map_err(|e| format!("Invalid value for {flag}: {e}"));
+        builder = match flag.as_str() {
+            "--port" => {
+                let port = value.parse::<u16>().map_err(|e| format!("Invalid port: {e}"))?;
+                builder.bind(SocketAddr::from(([127, 0, 0, 1], port)))
+            }
+            "--fixtures" => builder.fixtures_dir(value),
+            "--per-minute" => builder.per_minute_limit(number(&value)?),
+            "--daily" => builder.daily_limit(number(&value)?),
+            "--premium" => builder.premium_function(value),
+            "--premium-key" => builder.premium_key(value),
+            _ => return Err(format!("Unknown argument: {flag}\n{USAGE}")),
+        };
+    }
+    Ok(builder)
+}
+
+#[tokio::main]
+async fn main() {
+    let builder = match parse_args() {
+        Ok(builder) => builder,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(2);
+        }
+    };
+
+    let server = match builder.start().await {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Failed to start server: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    println!("Fake Alpha Vantage listening on {}", server.base_url());
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        eprintln!("Failed to wait for Ctrl-C: {e}");
+    }
+}
@@ -0,0 +1,349 @@
+//! End-to-end tests running the alphav client against the fake server
+
+use alphav::key_pool::{KeyPool, Strategy};
+use alphav::request::{HyperClient, HyperClientBuilder};
+use alphav::tool_use::call_tool;
+use alphav::{AlphaVantage, Request, Response, rest};
+use alphav_fake::FakeServer;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+
+fn client(server: &FakeServer, key: &str) -> AlphaVantage {
+    AlphaVantage::default().with_key(key).with_base_url(server.base_url())
+}
+
+#[tokio::test]
+async fn serves_json_and_csv_fixtures() {
+    let server = FakeServer::builder().fixtures_dir(FIXTURES).start().await.unwrap();
+    let client = client(&server, "test");
+
+    let json = rest::time_series::daily(&client, "IBM").get().await.unwrap();
+    assert!(json.contains("Time Series (Daily)"));
+
+    let csv = rest::time_series::daily(&client, "IBM")
+        .datatype("csv")
+        .get()
+        .await
+        .unwrap();
+    assert!(csv.starts_with("timestamp,open,high,low,close,volume"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].contains("function=TIME_SERIES_DAILY"));
+}
+
+#[tokio::test]
+async fn decodes_daily_series_ascending() {
+    let server = FakeServer::builder().fixtures_dir(FIXTURES).start().await.unwrap();
+    let client = client(&server, "test");
+
+    let series = rest::decoded::time_series::daily(&client, "IBM").get().await.unwrap();
+    assert_eq!(series.meta.symbol, "IBM");
+    assert_eq!(series.meta.time_zone, "US/Eastern");
+    assert!(series.bars.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+
+    let last = series.bars.last().unwrap();
+    assert_eq!(last.timestamp, series.meta.last_refreshed);
+    assert_eq!(last.close, 223.18);
+    assert_eq!(last.volume, 2_796_447);
+}
+
+#[tokio::test]
+async fn decodes_company_overview() {
+    let server = FakeServer::builder().fixtures_dir(FIXTURES).start().await.unwrap();
+    let client = client(&server, "test");
+
+    let overview = rest::decoded::fundamentals::company_overview(&client, "IBM")
+        .get()
+        .await
+        .unwrap();
+    assert_eq!(overview.name, "International Business Machines");
+    assert_eq!(overview.market_capitalization, Some(206_357_250_000));
+    assert_eq!(overview.dividend_yield, Some(0.0302));
+    assert_eq!(overview.week_52_high, Some(239.35));
+    assert_eq!(overview.shares_float, None);
+    assert_eq!(overview.ex_dividend_date.unwrap().to_string(), "2024-11-12");
+}
+
+#[tokio::test]
+async fn invalid_symbol_surfaces_upstream_message() {
+    let server = FakeServer::builder().fixtures_dir(FIXTURES).start().await.unwrap();
+    let client = client(&server, "test");
+
+    let err = call_tool(
+        &client,
+        json!({ "tool": "time_series_daily", "params": { "symbol": "NOPE" } }),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Invalid API call"), "got: {err}");
+}
+
+#[tokio::test]
+async fn fixture_lookup_stays_inside_fixtures_dir() {
+    let dir = format!("{FIXTURES}/TIME_SERIES_DAILY");
+    let server = FakeServer::builder().fixtures_dir(dir).start().await.unwrap();
+
+    let url = format!("{}/query?function=../OVERVIEW/IBM&apikey=test", server.base_url());
+    let response = HyperClient::new().get(&url).await.unwrap();
+    assert!(response.body().contains("Invalid API call"), "got: {}", response.body());
+}
+
+#[tokio::test]
+async fn per_minute_quota_is_tracked_per_key() {
+    let server = FakeServer::builder()
+        .fixture("OVERVIEW", Some("IBM"), r#"{"Symbol": "IBM"}"#)
+        .per_minute_limit(2)
+        .start()
+        .await
+        .unwrap();
+
+    let first = client(&server, "first");
+    for _ in 0..2 {
+        let body = rest::fundamentals::company_overview(&first, "IBM").get().await.unwrap();
+        assert!(body.contains("\"Symbol\""), "got: {body}");
+    }
+    let body = rest::fundamentals::company_overview(&first, "IBM").get().await.unwrap();
+    assert!(
+        body.contains("\"Note\"") && body.contains("5 calls per minute"),
+        "got: {body}"
+    );
+
+    // Another key has its own quota
+    let second = client(&server, "second");
+    let body = rest::fundamentals::company_overview(&second, "IBM")
+        .get()
+        .await
+        .unwrap();
+    assert!(body.contains("\"Symbol\""), "got: {body}");
+}
+
+#[tokio::test]
+async fn daily_quota_exhaustion() {
+    let server = FakeServer::builder()
+        .fixture("OVERVIEW", Some("IBM"), r#"{"Symbol": "IBM"}"#)
+        .daily_limit(1)
+        .start()
+        .await
+        .unwrap();
+    let client = client(&server, "test");
+
+    rest::fundamentals::company_overview(&client, "IBM")
+        .get()
+        .await
+        .unwrap();
+    let err = call_tool(
+        &client,
+        json!({ "tool": "time_series_daily", "params": { "symbol": "IBM" } }),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("25 requests per day"), "got: {err}");
+}
+
+#[tokio::test]
+async fn premium_endpoints_require_premium_key() {
+    let server = FakeServer::builder()
+        .fixtures_dir(FIXTURES)
+        .premium_key("premium")
+        .start()
+        .await
+        .unwrap();
+
+    let free = client(&server, "free");
+    let body = rest::quotes::realtime_bulk(&free, ["IBM", "SPY"]).get().await.unwrap();
+    assert!(body.contains("This is a premium endpoint"), "got: {body}");
+
+    let premium = client(&server, "premium");
+    let body = rest::quotes::realtime_bulk(&premium, ["IBM", "SPY", "NOPE"])
+        .get()
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let symbols: Vec<&str> = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|q| q["symbol"].as_str().unwrap())
+        .collect();
+    assert_eq!(symbols, ["IBM", "SPY"]);
+}
+
+#[tokio::test]
+async fn key_pool_rotates_on_rate_limit_note() {
+    let server = FakeServer::builder()
+        .fixture("OVERVIEW", Some("IBM"), r#"{"Symbol": "IBM"}"#)
+        .per_minute_limit(1)
+        .start()
+        .await
+        .unwrap();
+    // The pool itself knows no quota, so it only learns from the server's notes
+    let pool = KeyPool::new(["first", "second"]).strategy(Strategy::LeastUsed);
+    let client = AlphaVantage::default()
+        .with_key_pool(pool.clone())
+        .with_base_url(server.base_url());
+
+    for _ in 0..2 {
+        let body = rest::fundamentals::company_overview(&client, "IBM")
+            .get()
+            .await
+            .unwrap();
+        assert!(body.contains("\"Symbol\""), "got: {body}");
+    }
+    // Both keys are now benched after retrying through the pool
+    let err = rest::fundamentals::company_overview(&client, "IBM")
+        .get()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, alphav::Error::RateLimited { .. }), "got: {err:?}");
+
+    let usage = pool.usage();
+    assert!(usage.iter().all(|u| u.limited_for.is_some()));
+    assert_eq!(server.requests().len(), 4);
+}
+
+/// Minimal forward proxy recording the request line of each connection.
+///
+/// `CONNECT` requests are tunnelled; absolute-form requests are forwarded in
+/// origin form.
+async fn forward_proxy() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = requests.clone();
+    tokio::spawn(async move {
+        while let Ok((mut client, _)) = listener.accept().await {
+            let seen = seen.clone();
+            tokio::spawn(async move {
+                let mut head = Vec::new();
+                while !head.ends_with(b"\r\n\r\n") {
+                    let mut byte = [0u8];
+                    if client.read(&mut byte).await.unwrap_or(0) == 0 {
+                        return;
+                    }
+                    head.push(byte[0]);
+                }
+                let head = String::from_utf8_lossy(&head).into_owned();
+                let Some((line, rest)) = head.split_once("\r\n") else {
+                    return;
+                };
+                seen.lock().unwrap().push(line.to_string());
+                let mut parts = line.split(' ');
+                let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next()) else {
+                    return;
+                };
+
+                let mut upstream = if method == "CONNECT" {
+                    let upstream = TcpStream::connect(target).await.unwrap();
+                    client
+                        .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                        .await
+                        .unwrap();
+                    upstream
+                } else {
+                    let Some(target) = target.strip_prefix("http://") else {
+                        return;
+                    };
+                    let (authority, path) = target.split_at(target.find('/').unwrap_or(target.len()));
+                    let mut upstream = TcpStream::connect(authority).await.unwrap();
+                    let head = format!("{method} {path} {version}\r\n{rest}");
+                    upstream.write_all(head.as_bytes()).await.unwrap();
+                    upstream
+                };
+                let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+            });
+        }
+    });
+    (url, requests)
+}
+
+#[tokio::test]
+async fn requests_go_through_configured_proxy() {
+    let server = FakeServer::builder().fixtures_dir(FIXTURES).start().await.unwrap();
+    let (proxy, requests) = forward_proxy().await;
+
+    let http = HyperClientBuilder::new().proxy(proxy).build().unwrap();
+    let client = alphav::client::AlphaVantage::<HyperClient>::default()
+        .with_client(http)
+        .with_key("test")
+        .with_base_url(server.base_url());
+
+    let json = rest::time_series::daily(&client, "IBM").get().await.unwrap();
+    assert!(json.contains("Time Series (Daily)"));
+    // Plain HTTP goes to the proxy in absolute form rather than through a tunnel
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    assert!(
+        requests[0].starts_with(&format!("GET http://{}/query?", server.addr())),
+        "got: {requests:?}"
+    );
+}
+
+#[test]
+fn blocking_client_without_async_runtime() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime
+        .block_on(FakeServer::builder().fixtures_dir(FIXTURES).start())
+        .unwrap();
+    let client = alphav::blocking::AlphaVantage::new(client(&server, "test"));
+
+    let csv = alphav::blocking::rest::time_series::daily(&client, "IBM")
+        .with(|daily| daily.datatype("csv"))
+        .get()
+        .unwrap();
+    assert!(csv.starts_with("timestamp,open,high,low,close,volume"));
+
+    alphav::blocking::tool_use::call_tool(
+        &client,
+        json!({ "tool": "time_series_daily", "params": { "symbol": "IBM" } }),
+    )
+    .unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn chunked_bulk_quotes_merge_batches() {
+    let mut builder = FakeServer::builder().premium_key("premium");
+    let symbols: Vec<String> = (0..150).map(|i| format!("S{i:03}")).collect();
+    for symbol in &symbols {
+        builder = builder.fixture(
+            "REALTIME_BULK_QUOTES",
+            Some(symbol),
+            json!({ "symbol": symbol, "close": "1.00" }).to_string(),
+        );
+    }
+    let server = builder.start().await.unwrap();
+    let client = client(&server, "premium");
+
+    let requested = symbols.iter().map(String::as_str).chain(["S000", "S149", "NOPE"]);
+    let quotes = rest::quotes::realtime_bulk(&client, requested)
+        .chunked()
+        .get()
+        .await
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&quotes.quotes).unwrap();
+    assert_eq!(json["data"].as_array().unwrap().len(), 150);
+    assert_eq!(quotes.missing, ["NOPE"]);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn single_symbol_bulk_quotes_keep_envelope() {
+    let server = FakeServer::builder()
+        .fixtures_dir(FIXTURES)
+        .premium_key("premium")
+        .start()
+        .await
+        .unwrap();
+    let client = client(&server, "premium");
+
+    let body = rest::quotes::realtime_bulk(&client, ["IBM"]).get().await.unwrap();
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["endpoint"], "Realtime Bulk Quotes");
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+}
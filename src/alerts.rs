@@ -0,0 +1,446 @@
+//! Price and volume alerts evaluated against incoming quotes
+//!
+//! Register [`Rule`]s with an [`AlertEngine`], then feed it quotes, usually from a
+//! [`QuoteStream`](crate::quote_stream::QuoteStream). Alerts are returned by
+//! [`evaluate`](AlertEngine::evaluate) and also delivered to every callback and
+//! channel registered with the engine.
+//!
+//! A rule fires when its condition starts to hold, not on every quote while it
+//! keeps holding, and never twice within its cooldown.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use alphav::AlphaVantage;
+//! use alphav::alerts::{AlertEngine, Rule};
+//! use alphav::quote_stream::QuoteStream;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let client = AlphaVantage::default().with_key("your_api_key");
+//! let quotes = QuoteStream::builder(&client, ["IBM", "AAPL"]).start();
+//!
+//! let mut engine = AlertEngine::new()
+//!     .rule(Rule::crosses_above("IBM", 250.0))
+//!     .rule(Rule::move_from_open("AAPL", 3.0).cooldown(Duration::from_secs(30 * 60)))
+//!     .rule(Rule::volume_spike("AAPL", 5.0, 10))
+//!     .on_alert(|alert| println!("{alert}"));
+//! engine.run(quotes).await;
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::pin;
+use std::time::{Duration, Instant};
+
+use futures_util::stream::{Stream, StreamExt};
+use tokio::sync::mpsc;
+
+use crate::error::Result;
+use crate::quote_stream::Quote;
+
+/// Default minimum time between two alerts of the same rule
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+/// What a [`Rule`] watches for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Condition {
+    /// Price rises from below `level` to or above it
+    CrossesAbove(f64),
+    /// Price falls from above `level` to or below it
+    CrossesBelow(f64),
+    /// Price is at least this many percent away from the session open, either way
+    MoveFromOpen(f64),
+    /// Volume traded since the previous quote is at least `multiple` times the
+    /// average of the previous `lookback` such intervals
+    VolumeSpike {
+        /// Multiple of the average that counts as a spike
+        multiple: f64,
+        /// Number of previous intervals averaged
+        lookback: usize,
+    },
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::CrossesAbove(level) => write!(f, "crosses above {level}"),
+            Condition::CrossesBelow(level) => write!(f, "crosses below {level}"),
+            Condition::MoveFromOpen(percent) => write!(f, "moves {percent}% from open"),
+            Condition::VolumeSpike { multiple, lookback } => {
+                write!(f, "volume {multiple}x the average of {lookback} intervals")
+            }
+        }
+    }
+}
+
+/// A condition on one symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    /// Name identifying the rule in alerts, e.g. for [`AlertEngine::remove_rule`]
+    pub name: String,
+    /// Stock symbol, upper-cased
+    pub symbol: String,
+    /// Condition to watch for
+    pub condition: Condition,
+    /// Minimum time between two alerts of this rule
+    pub cooldown: Duration,
+}
+
+impl Rule {
+    /// Watch `symbol` for `condition`. The rule is named after both.
+    pub fn new(symbol: impl Into<String>, condition: Condition) -> Self {
+        let symbol = symbol.into().to_ascii_uppercase();
+        Self {
+            name: format!("{symbol} {condition}"),
+            symbol,
+            condition,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// Alert when the price rises through `level`.
+    pub fn crosses_above(symbol: impl Into<String>, level: f64) -> Self {
+        Self::new(symbol, Condition::CrossesAbove(level))
+    }
+
+    /// Alert when the price falls through `level`.
+    pub fn crosses_below(symbol: impl Into<String>, level: f64) -> Self {
+        Self::new(symbol, Condition::CrossesBelow(level))
+    }
+
+    /// Alert when the price moves `percent` or more from the session open.
+    pub fn move_from_open(symbol: impl Into<String>, percent: f64) -> Self {
+        Self::new(symbol, Condition::MoveFromOpen(percent))
+    }
+
+    /// Alert when the volume between two quotes reaches `multiple` times the
+    /// average of the previous `lookback` intervals.
+    pub fn volume_spike(symbol: impl Into<String>, multiple: f64, lookback: usize) -> Self {
+        Self::new(symbol, Condition::VolumeSpike { multiple, lookback })
+    }
+
+    /// Set the name.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the cooldown. Defaults to [`DEFAULT_COOLDOWN`].
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+/// A rule that fired
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    /// Name of the rule
+    pub rule: String,
+    /// Condition that started to hold
+    pub condition: Condition,
+    /// Quote that triggered the alert
+    pub quote: Quote,
+    /// Measured value: the price for crossings, the percent move from open, or
+    /// the volume as a multiple of its average
+    pub value: f64,
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} at {}", self.rule, self.quote.symbol, self.quote.price)
+    }
+}
+
+/// A rule with what it has seen so far
+#[derive(Debug)]
+struct Armed {
+    rule: Rule,
+    /// Whether the condition held at the previous quote
+    holding: bool,
+    last_fired: Option<Instant>,
+    last_price: Option<f64>,
+    last_volume: Option<f64>,
+    intervals: VecDeque<f64>,
+}
+
+impl Armed {
+    fn new(rule: Rule) -> Self {
+        Self {
+            rule,
+            holding: false,
+            last_fired: None,
+            last_price: None,
+            last_volume: None,
+            intervals: VecDeque::new(),
+        }
+    }
+
+    /// Record `quote` and return the measured value if the condition holds.
+    fn check(&mut self, quote: &Quote) -> Option<f64> {
+        let price = quote.price;
+        match self.rule.condition {
+            Condition::CrossesAbove(level) => {
+                let crossed = self.last_price.replace(price).is_some_and(|last| last < level);
+                (crossed && price >= level).then_some(price)
+            }
+            Condition::CrossesBelow(level) => {
+                let crossed = self.last_price.replace(price).is_some_and(|last| last > level);
+                (crossed && price <= level).then_some(price)
+            }
+            Condition::MoveFromOpen(percent) => {
+                let open = quote.open.filter(|open| *open != 0.0)?;
+                let moved = (price - open) / open * 100.0;
+                (moved.abs() >= percent).then_some(moved)
+            }
+            Condition::VolumeSpike { multiple, lookback } => {
+                let volume = quote.volume?;
+                // Volume is cumulative per session; a drop means a new session started
+                let interval = match self.last_volume.replace(volume) {
+                    Some(last) if volume >= last => volume - last,
+                    _ => return None,
+                };
+                let average = (!self.intervals.is_empty())
+                    .then(|| self.intervals.iter().sum::<f64>() / self.intervals.len() as f64);
+                self.intervals.push_back(interval);
+                while self.intervals.len() > lookback.max(1) {
+                    self.intervals.pop_front();
+                }
+                let average = average.filter(|average| *average > 0.0)?;
+                (interval >= multiple * average).then_some(interval / average)
+            }
+        }
+    }
+
+    fn evaluate(&mut self, quote: &Quote, now: Instant) -> Option<Alert> {
+        let value = self.check(quote);
+        let started = value.is_some() && !self.holding;
+        self.holding = value.is_some();
+
+        let cooled_down = self
+            .last_fired
+            .is_none_or(|fired| now.duration_since(fired) >= self.rule.cooldown);
+        if !(started && cooled_down) {
+            return None;
+        }
+        self.last_fired = Some(now);
+        Some(Alert {
+            rule: self.rule.name.clone(),
+            condition: self.rule.condition,
+            quote: quote.clone(),
+            value: value?,
+        })
+    }
+}
+
+type Callback = Box<dyn Fn(&Alert) + Send + Sync>;
+
+/// Evaluates rules against quotes and delivers the resulting alerts
+#[derive(Default)]
+pub struct AlertEngine {
+    rules: Vec<Armed>,
+    callbacks: Vec<Callback>,
+    channels: Vec<mpsc::UnboundedSender<Alert>>,
+}
+
+impl fmt::Debug for AlertEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlertEngine")
+            .field("rules", &self.rules().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl AlertEngine {
+    /// Create an engine without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule.
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.add_rule(rule);
+        self
+    }
+
+    /// Call `callback` with every alert.
+    pub fn on_alert(mut self, callback: impl Fn(&Alert) + Send + Sync + 'static) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Add a rule.
+    pub fn add_rule(&mut self, rule: Rule) {
+        self.rules.push(Armed::new(rule));
+    }
+
+    /// Remove the rules named `name`. Returns whether there were any.
+    pub fn remove_rule(&mut self, name: &str) -> bool {
+        let before = self.rules.len();
+        self.rules.retain(|armed| armed.rule.name != name);
+        self.rules.len() != before
+    }
+
+    /// Registered rules.
+    pub fn rules(&self) -> impl Iterator<Item = &Rule> {
+        self.rules.iter().map(|armed| &armed.rule)
+    }
+
+    /// Receive every alert from now on through a channel. Alerts for a dropped
+    /// receiver are discarded.
+    pub fn subscribe(&mut self) -> mpsc::UnboundedReceiver<Alert> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.channels.push(sender);
+        receiver
+    }
+
+    /// Evaluate every rule for the quote's symbol, deliver the alerts and return them.
+    pub fn evaluate(&mut self, quote: &Quote) -> Vec<Alert> {
+        self.evaluate_at(quote, Instant::now())
+    }
+
+    /// Evaluate quotes until the stream ends. Failed polls are skipped.
+    pub async fn run(&mut self, quotes: impl Stream<Item = Result<Quote>>) {
+        let mut quotes = pin!(quotes);
+        while let Some(quote) = quotes.next().await {
+            if let Ok(quote) = quote {
+                self.evaluate(&quote);
+            }
+        }
+    }
+
+    fn evaluate_at(&mut self, quote: &Quote, now: Instant) -> Vec<Alert> {
+        let symbol = quote.symbol.to_ascii_uppercase();
+        let alerts: Vec<Alert> = self
+            .rules
+            .iter_mut()
+            .filter(|armed| armed.rule.symbol == symbol)
+            .filter_map(|armed| armed.evaluate(quote, now))
+            .collect();
+
+        self.channels.retain(|channel| !channel.is_closed());
+        for alert in &alerts {
+            for callback in &self.callbacks {
+                callback(alert);
+            }
+            for channel in &self.channels {
+                let _ = channel.send(alert.clone());
+            }
+        }
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::client::AlphaVantage;
+    use crate::mock::{Matcher, MockClient, MockResponse};
+    use crate::quote_stream::QuoteStream;
+
+    /// Mock answering each request with the next scripted `(price, volume)` as
+    /// a global quote
+    fn scripted(script: &[(f64, f64)]) -> MockClient {
+        let quotes = script.iter().map(|(price, volume)| {
+            let body = serde_json::json!({
+                "Global Quote": {
+                    "01. symbol": "IBM",
+                    "02. open": "100.0000",
+                    "05. price": price.to_string(),
+                    "06. volume": volume.to_string(),
+                }
+            });
+            MockResponse::ok(body.to_string())
+        });
+        let mock = MockClient::default();
+        mock.on_sequence(Matcher::any(), quotes);
+        mock
+    }
+
+    fn quote(price: f64) -> Quote {
+        Quote {
+            symbol: "IBM".to_string(),
+            price,
+            open: None,
+            high: None,
+            low: None,
+            volume: None,
+            previous_close: None,
+            change: None,
+            change_percent: None,
+            timestamp: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn alerts_on_scripted_quotes() {
+        let script = [
+            (99.0, 1000.0),
+            (101.0, 1100.0),
+            (102.0, 1200.0),
+            (99.0, 1300.0),
+            (103.0, 2300.0),
+        ];
+        let client = AlphaVantage::default().with_client(scripted(&script)).with_key("test");
+        let quotes = QuoteStream::builder(&client, ["IBM"])
+            .interval(Duration::from_millis(1))
+            .market_hours(None)
+            .start()
+            .take(script.len());
+
+        let called = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&called);
+        let mut engine = AlertEngine::new()
+            .rule(Rule::crosses_above("ibm", 100.0).cooldown(Duration::ZERO))
+            .rule(Rule::move_from_open("IBM", 2.0).cooldown(Duration::ZERO))
+            .rule(Rule::volume_spike("IBM", 5.0, 3).name("IBM volume"))
+            .on_alert(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+        let mut alerts = engine.subscribe();
+        engine.run(quotes).await;
+
+        let mut fired = Vec::new();
+        while let Ok(alert) = alerts.try_recv() {
+            fired.push((alert.rule, alert.quote.price));
+        }
+        let expected = [
+            ("IBM crosses above 100", 101.0),
+            ("IBM moves 2% from open", 102.0),
+            ("IBM crosses above 100", 103.0),
+            ("IBM moves 2% from open", 103.0),
+            ("IBM volume", 103.0),
+        ];
+        assert_eq!(fired, expected.map(|(rule, price)| (rule.to_string(), price)));
+        assert_eq!(called.load(Ordering::SeqCst), expected.len());
+    }
+
+    #[test]
+    fn cooldown_and_dedupe() {
+        let start = Instant::now();
+        let mut engine = AlertEngine::new().rule(Rule::crosses_below("IBM", 50.0).cooldown(Duration::from_secs(60)));
+        let mut fire = |price: f64, secs: u64| {
+            engine
+                .evaluate_at(&quote(price), start + Duration::from_secs(secs))
+                .len()
+        };
+
+        assert_eq!(fire(51.0, 0), 0);
+        assert_eq!(fire(49.0, 1), 1);
+        // Still below: no new crossing
+        assert_eq!(fire(48.0, 2), 0);
+        // Crosses again within the cooldown
+        assert_eq!(fire(51.0, 3), 0);
+        assert_eq!(fire(49.0, 4), 0);
+        // And after it
+        assert_eq!(fire(51.0, 70), 0);
+        assert_eq!(fire(49.0, 71), 1);
+
+        assert!(engine.remove_rule("IBM crosses below 50"));
+        assert_eq!(engine.rules().count(), 0);
+    }
+}
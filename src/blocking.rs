@@ -0,0 +1,258 @@
+//! Blocking (synchronous) facade over the async client
+//!
+//! For scripts and CLIs that don't run an async runtime. The blocking
+//! [`AlphaVantage`] owns a Tokio runtime and drives the async client on it,
+//! much like `reqwest::blocking`. The [`rest`] functions mirror the raw
+//! [`crate::rest`] endpoints, and [`tool_use::call_tool`] mirrors
+//! [`crate::tool_use::call_tool`]. There are no blocking `rest::decoded` or
+//! `rest::table` modules; switch the processor through [`Blocking::with`]
+//! instead, e.g. `.with(|quote| quote.as_decoded())`, or wrap any async
+//! builder made from [`AlphaVantage::inner`] with [`AlphaVantage::request`].
+//!
+//! ```no_run
+//! use alphav::blocking::{self, AlphaVantage};
+//! use alphav::request::common::OutputSize;
+//!
+//! fn main() -> alphav::Result<()> {
+//!     let client = AlphaVantage::new(alphav::AlphaVantage::default().with_key("your_api_key"));
+//!     let json = blocking::rest::time_series::daily(&client, "IBM")
+//!         .with(|daily| daily.outputsize(OutputSize::Full))
+//!         .get()?;
+//!     println!("{json}");
+//!     Ok(())
+//! }
+//! ```
+//!
+//! # Panics
+//!
+//! Running a request ([`Blocking::get`], [`AlphaVantage::block_on`] or the
+//! [`tool_use`] functions) or dropping the last clone of a blocking client from
+//! within an async runtime panics, as with any nested Tokio runtime. Creating
+//! one there is fine. Use the async client in async code.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::runtime::Runtime;
+
+use crate::error::Result;
+use crate::execute::Execute;
+use crate::request::Request;
+use crate::secret::ApiKey;
+
+pub mod rest;
+
+#[cfg(feature = "reqwest")]
+type DefaultClient = reqwest::Client;
+
+#[cfg(all(feature = "hyper", not(feature = "reqwest")))]
+type DefaultClient = crate::request::HyperClient;
+
+/// Synchronous Alpha Vantage client
+///
+/// Wraps an async [`client::AlphaVantage`](crate::client::AlphaVantage) and a
+/// runtime to run it on. Clones share the runtime.
+#[derive(Debug, Clone)]
+pub struct AlphaVantage<Client: Request = DefaultClient> {
+    inner: crate::client::AlphaVantage<Client>,
+    runtime: Arc<Runtime>,
+}
+
+impl<Client: Request> AlphaVantage<Client> {
+    /// Wrap a configured async client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be created.
+    pub fn new(inner: crate::client::AlphaVantage<Client>) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to create the blocking client runtime");
+        Self {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// Set the API key for this instance.
+    pub fn with_key(mut self, api_key: impl Into<ApiKey>) -> Self {
+        self.inner = self.inner.with_key(api_key);
+        self
+    }
+
+    /// Get the wrapped async client.
+    pub fn inner(&self) -> &crate::client::AlphaVantage<Client> {
+        &self.inner
+    }
+
+    /// Wrap an async request builder made from [`inner`](Self::inner) so it can
+    /// be run synchronously.
+    pub fn request<B: Execute>(&self, builder: B) -> Blocking<'_, B> {
+        Blocking {
+            runtime: &self.runtime,
+            builder,
+        }
+    }
+
+    /// Run any future on this client's runtime and wait for its result.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+impl<Client: Request> Default for AlphaVantage<Client> {
+    /// Wrap a default async client with no API key set.
+    fn default() -> Self {
+        Self::new(crate::client::AlphaVantage::default())
+    }
+}
+
+impl<Client: Request> From<crate::client::AlphaVantage<Client>> for AlphaVantage<Client> {
+    fn from(inner: crate::client::AlphaVantage<Client>) -> Self {
+        Self::new(inner)
+    }
+}
+
+/// An async request builder bound to a blocking client's runtime
+pub struct Blocking<'a, B> {
+    runtime: &'a Runtime,
+    builder: B,
+}
+
+impl<'a, B: Execute> Blocking<'a, B> {
+    /// Configure the underlying async builder, e.g. to set options or a processor.
+    pub fn with<B2: Execute>(self, configure: impl FnOnce(B) -> B2) -> Blocking<'a, B2> {
+        Blocking {
+            runtime: self.runtime,
+            builder: configure(self.builder),
+        }
+    }
+
+    /// Execute the request, blocking until the result is available.
+    pub fn get(self) -> Result<B::Output> {
+        self.runtime.block_on(self.builder.get())
+    }
+}
+
+/// Blocking LLM tool use interface
+pub mod tool_use {
+    use serde_json::Value;
+
+    use super::AlphaVantage;
+    use crate::error::Result;
+    use crate::request::Request;
+    use crate::tool_use::ToolResult;
+
+    /// Universal tool caller, blocking until the result is available
+    pub fn call_tool<Client: Request>(client: &AlphaVantage<Client>, request: Value) -> Result<ToolResult> {
+        client.block_on(crate::tool_use::call_tool(client.inner(), request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Matcher, MockClient, MockResponse};
+    use crate::request::common::OutputSize;
+    use crate::tool_use::ToolResult;
+
+    #[test]
+    fn runs_builders_and_tools_synchronously() {
+        let mock = MockClient::default();
+        mock.on(Matcher::function("TIME_SERIES_DAILY"), MockResponse::ok("{}"));
+        let client = AlphaVantage::new(
+            crate::client::AlphaVantage::default()
+                .with_client(mock.clone())
+                .with_key("test"),
+        );
+
+        let body = rest::time_series::daily(&client, "IBM")
+            .with(|daily| daily.outputsize(OutputSize::Full))
+            .get()
+            .unwrap();
+        assert_eq!(body, "{}");
+        let query = &mock.requests()[0];
+        assert!(
+            query.contains("symbol=IBM") && query.contains("outputsize=full"),
+            "got: {query}"
+        );
+
+        let request = serde_json::json!({ "tool": "time_series_daily", "params": { "symbol": "IBM" } });
+        assert!(tool_use::call_tool(&client, request).is_err());
+    }
+
+    const DAILY: &str = r#"{
+        "Meta Data": {"2. Symbol": "IBM", "5. Time Zone": "US/Eastern"},
+        "Time Series (Daily)": {
+            "2025-01-08": {"1. open": "222.9500", "2. high": "223.6900", "3. low": "220.8100", "4. close": "223.1800", "5. volume": "2796447"},
+            "2025-01-07": {"1. open": "224.5000", "2. high": "226.7100", "3. low": "221.9200", "4. close": "222.5100", "5. volume": "3325563"}
+        }
+    }"#;
+
+    fn daily_client() -> AlphaVantage<MockClient> {
+        let mock = MockClient::default();
+        mock.on(
+            Matcher::function("TIME_SERIES_DAILY").symbol("IBM"),
+            MockResponse::ok(DAILY),
+        );
+        AlphaVantage::new(
+            crate::client::AlphaVantage::default()
+                .with_client(mock)
+                .with_key("test"),
+        )
+    }
+
+    #[test]
+    fn returns_response_bodies_and_tool_tables() {
+        let client = daily_client();
+
+        let body = rest::time_series::daily(&client, "IBM").get().unwrap();
+        assert_eq!(body, DAILY);
+
+        let request = serde_json::json!({ "tool": "time_series_daily", "params": { "symbol": "IBM" } });
+        let ToolResult::DataFrame(frame) = tool_use::call_tool(&client, request).unwrap() else {
+            panic!("time_series_daily should return a table");
+        };
+        assert_eq!(frame.data[0]["date"], "2025-01-08");
+        assert_eq!(frame.data[1]["close"], 222.51);
+    }
+
+    #[cfg(feature = "table")]
+    #[test]
+    fn runs_table_builders_through_with() {
+        let client = daily_client();
+
+        let df = rest::time_series::daily(&client, "IBM")
+            .with(|daily| daily.as_dataframe())
+            .get()
+            .unwrap();
+        assert_eq!(df.shape(), (2, 6));
+        let close = df.column("close").unwrap().f64().unwrap();
+        assert_eq!(close.get(0), Some(223.18));
+    }
+
+    #[cfg(feature = "decoder")]
+    #[test]
+    fn switches_processor_through_with() {
+        let mock = MockClient::default();
+        mock.on(
+            Matcher::function("GLOBAL_QUOTE"),
+            MockResponse::ok(
+                r#"{"Global Quote": {"01. symbol": "IBM", "05. price": "223.2000", "06. volume": "100",
+                    "07. latest trading day": "2025-01-08"}}"#,
+            ),
+        );
+        let client = AlphaVantage::new(
+            crate::client::AlphaVantage::default()
+                .with_client(mock.clone())
+                .with_key("test"),
+        );
+
+        let quote = rest::quotes::global_quote(&client, "IBM")
+            .with(|quote| quote.as_decoded())
+            .get()
+            .unwrap();
+        assert_eq!(quote.symbol, "IBM");
+    }
+}
@@ -0,0 +1,135 @@
+//! Blocking REST API endpoints
+//!
+//! Each function mirrors its raw counterpart in [`crate::rest`] and returns the
+//! same request builder wrapped in [`Blocking`]. For decoded structs or tables,
+//! change the builder's processor with [`Blocking::with`].
+
+use super::{AlphaVantage, Blocking};
+use crate::request::Request;
+
+/// Time series endpoints
+pub mod time_series {
+    use super::*;
+    use crate::processor::Raw;
+    use crate::request::common::Interval;
+    use crate::request::time_series::{TimeSeriesDaily, TimeSeriesIntraday, TimeSeriesMonthly, TimeSeriesWeekly};
+
+    /// Get intraday time series for a stock
+    pub fn intraday<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+        interval: Interval,
+    ) -> Blocking<'_, TimeSeriesIntraday<'_, Client, Raw>> {
+        client.request(crate::rest::time_series::intraday(client.inner(), symbol, interval))
+    }
+
+    /// Get daily time series for a stock
+    pub fn daily<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, TimeSeriesDaily<'_, Client, Raw>> {
+        client.request(crate::rest::time_series::daily(client.inner(), symbol))
+    }
+
+    /// Get weekly time series for a stock
+    pub fn weekly<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, TimeSeriesWeekly<'_, Client, Raw>> {
+        client.request(crate::rest::time_series::weekly(client.inner(), symbol))
+    }
+
+    /// Get monthly time series for a stock
+    pub fn monthly<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, TimeSeriesMonthly<'_, Client, Raw>> {
+        client.request(crate::rest::time_series::monthly(client.inner(), symbol))
+    }
+}
+
+/// Fundamental data endpoints
+pub mod fundamentals {
+    use super::*;
+    use crate::processor::Raw;
+    use crate::request::fundamentals::{
+        BalanceSheet, CashFlow, CompanyOverview, Earnings, EarningsEstimates, IncomeStatement,
+    };
+
+    /// Get earnings estimates for a company
+    pub fn earnings_estimates<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, EarningsEstimates<'_, Client, Raw>> {
+        client.request(crate::rest::fundamentals::earnings_estimates(client.inner(), symbol))
+    }
+
+    /// Get earnings data for a company
+    pub fn earnings<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, Earnings<'_, Client, Raw>> {
+        client.request(crate::rest::fundamentals::earnings(client.inner(), symbol))
+    }
+
+    /// Get company overview
+    pub fn company_overview<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, CompanyOverview<'_, Client, Raw>> {
+        client.request(crate::rest::fundamentals::company_overview(client.inner(), symbol))
+    }
+
+    /// Get income statement
+    pub fn income_statement<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, IncomeStatement<'_, Client, Raw>> {
+        client.request(crate::rest::fundamentals::income_statement(client.inner(), symbol))
+    }
+
+    /// Get balance sheet
+    pub fn balance_sheet<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, BalanceSheet<'_, Client, Raw>> {
+        client.request(crate::rest::fundamentals::balance_sheet(client.inner(), symbol))
+    }
+
+    /// Get cash flow statement
+    pub fn cash_flow<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, CashFlow<'_, Client, Raw>> {
+        client.request(crate::rest::fundamentals::cash_flow(client.inner(), symbol))
+    }
+}
+
+/// Quote endpoints
+pub mod quotes {
+    use super::*;
+    use crate::processor::Raw;
+    use crate::request::global_quote::GlobalQuote;
+    use crate::request::realtime_bulk_quotes::RealtimeBulkQuotes;
+
+    /// Get the latest quote for a single symbol
+    pub fn global_quote<Client: Request>(
+        client: &AlphaVantage<Client>,
+        symbol: impl Into<String>,
+    ) -> Blocking<'_, GlobalQuote<'_, Client, Raw>> {
+        client.request(crate::rest::quotes::global_quote(client.inner(), symbol))
+    }
+
+    /// Get realtime quotes for up to 100 symbols in one request
+    pub fn realtime_bulk<Client, I, S>(
+        client: &AlphaVantage<Client>,
+        symbols: I,
+    ) -> Blocking<'_, RealtimeBulkQuotes<'_, Client, Raw>>
+    where
+        Client: Request,
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        client.request(crate::rest::quotes::realtime_bulk(client.inner(), symbols))
+    }
+}
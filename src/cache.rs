@@ -0,0 +1,381 @@
+//! File-backed response cache with an offline mode
+//!
+//! [`CachedClient`] wraps any [`Request`] implementation and stores each successful
+//! response body verbatim on disk, one file per request fingerprint. The fingerprint
+//! is derived from the request URL with the `apikey` parameter removed, so cached
+//! responses are shared between keys and never contain credentials.
+//!
+//! In offline mode the wrapped client is never called: cached responses are served
+//! and a miss returns [`Error::CacheMiss`].
+//!
+//! ```no_run
+//! use alphav::{AlphaVantage, rest};
+//! use alphav::cache::CachedClient;
+//!
+//! # async fn example() -> alphav::Result<()> {
+//! let client = AlphaVantage::default()
+//!     .with_key("my_api_key")
+//!     .wrap_client(|http| CachedClient::wrap(http, ".alphav-cache").offline(true));
+//!
+//! let json = rest::time_series::daily(&client, "IBM").get().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::request::{HttpResponse, Request};
+use crate::response::Response;
+
+/// Environment variable overriding the default cache directory.
+pub const CACHE_DIR_ENV: &str = "ALPHAVANTAGE_CACHE_DIR";
+
+/// Environment variable enabling offline mode (`1` or `true`).
+pub const OFFLINE_ENV: &str = "ALPHAVANTAGE_OFFLINE";
+
+/// Cache directory used when [`CACHE_DIR_ENV`] is not set.
+pub const DEFAULT_CACHE_DIR: &str = ".alphav-cache";
+
+/// A cached response as stored on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Request URL with the API key removed
+    pub url: String,
+    /// Alpha Vantage `function` parameter of the request
+    pub function: Option<String>,
+    /// `symbol` parameter of the request, if any
+    pub symbol: Option<String>,
+    /// Fetch time as seconds since the Unix epoch
+    pub fetched_at: u64,
+    /// HTTP status code
+    pub status: u16,
+    /// Request ID if available
+    pub request_id: Option<String>,
+    /// Response body, exactly as returned by the API
+    pub body: String,
+}
+
+impl CacheEntry {
+    /// Time elapsed since the entry was fetched.
+    pub fn age(&self) -> Duration {
+        now().saturating_sub(Duration::from_secs(self.fetched_at))
+    }
+}
+
+/// HTTP client wrapper that serves responses from an on-disk cache.
+#[derive(Debug, Clone)]
+pub struct CachedClient<C: Request> {
+    inner: C,
+    dir: PathBuf,
+    offline: bool,
+    ttl: Option<Duration>,
+}
+
+impl<C: Request> CachedClient<C> {
+    /// Wrap `inner`, caching responses in `dir`.
+    pub fn wrap(inner: C, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            dir: dir.into(),
+            offline: false,
+            ttl: None,
+        }
+    }
+
+    /// Sets the wrapped HTTP client.
+    pub fn with_client(mut self, client: C) -> Self {
+        self.inner = client;
+        self
+    }
+
+    /// Sets the cache directory.
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// Serve only cached responses and never call the network.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Treat entries older than `ttl` as missing when online.
+    ///
+    /// Offline mode serves entries regardless of age.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Whether the client is in offline mode.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// The cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get a reference to the wrapped HTTP client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Path of the cache file for `url`.
+    pub fn path_for(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fingerprint(url)))
+    }
+
+    /// Read the cached entry for `url`, if any.
+    pub fn lookup(&self, url: &str) -> Result<Option<CacheEntry>> {
+        let path = self.path_for(url);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(Error::Custom(format!(
+                    "Failed to read cache entry {}: {e}",
+                    path.display()
+                )));
+            }
+        };
+        let entry = serde_json::from_str(&contents)
+            .map_err(|e| Error::Custom(format!("Corrupt cache entry {}: {e}", path.display())))?;
+        Ok(Some(entry))
+    }
+
+    /// Remove every entry from the cache directory.
+    pub fn clear(&self) -> Result<()> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(Error::Custom(format!("Failed to read cache directory: {e}"))),
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                std::fs::remove_file(&path)
+                    .map_err(|e| Error::Custom(format!("Failed to remove {}: {e}", path.display())))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn store(&self, url: &str, response: &HttpResponse) -> Result<()> {
+        let entry = CacheEntry {
+            url: strip_api_key(url),
+            function: query_param(url, "function"),
+            symbol: query_param(url, "symbol"),
+            fetched_at: now().as_secs(),
+            status: response.status(),
+            request_id: response.request_id().clone(),
+            body: response.body().to_owned(),
+        };
+
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| Error::Custom(format!("Failed to create cache directory: {e}")))?;
+
+        // Write to a temporary file first so readers never observe a partial entry
+        let path = self.path_for(url);
+        let tmp = path.with_extension("json.tmp");
+        let contents = serde_json::to_vec_pretty(&entry)?;
+        std::fs::write(&tmp, contents)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .map_err(|e| Error::Custom(format!("Failed to write cache entry {}: {e}", path.display())))
+    }
+
+    fn is_fresh(&self, entry: &CacheEntry) -> bool {
+        self.offline || self.ttl.is_none_or(|ttl| entry.age() <= ttl)
+    }
+}
+
+impl<C: Request> Request for CachedClient<C> {
+    type Response = HttpResponse;
+
+    /// Wrap a default HTTP client.
+    ///
+    /// The cache directory is read from [`CACHE_DIR_ENV`] (falling back to
+    /// [`DEFAULT_CACHE_DIR`]) and offline mode from [`OFFLINE_ENV`].
+    fn new() -> Self {
+        let dir = std::env::var(CACHE_DIR_ENV).unwrap_or_else(|_| DEFAULT_CACHE_DIR.to_string());
+        let offline = std::env::var(OFFLINE_ENV).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+        Self::wrap(C::new(), dir).offline(offline)
+    }
+
+    async fn get(&self, url: &str) -> Result<Self::Response> {
+        if let Some(entry) = self.lookup(url)?
+            && self.is_fresh(&entry)
+        {
+            return Ok(HttpResponse::new(entry.status, entry.body, entry.request_id));
+        }
+
+        if self.offline {
+            return Err(Error::CacheMiss {
+                url: strip_api_key(url),
+            });
+        }
+
+        let response = self.inner.get(url).await?;
+        let response = HttpResponse::new(
+            response.status(),
+            response.body().to_owned(),
+            response.request_id().clone(),
+        );
+        if is_cacheable(&response) {
+            self.store(url, &response)?;
+        }
+        Ok(response)
+    }
+
+    async fn post(&self, url: &str, body: &str) -> Result<Self::Response> {
+        if self.offline {
+            return Err(Error::CacheMiss {
+                url: strip_api_key(url),
+            });
+        }
+
+        let response = self.inner.post(url, body).await?;
+        Ok(HttpResponse::new(
+            response.status(),
+            response.body().to_owned(),
+            response.request_id().clone(),
+        ))
+    }
+}
+
+/// Only successful data responses are cached. Alpha Vantage reports rate limits,
+/// premium-only endpoints and invalid symbols with a 200 status and a single
+/// `Note`, `Information` or `Error Message` field, which must not be replayed.
+fn is_cacheable(response: &HttpResponse) -> bool {
+    if response.status() != 200 {
+        return false;
+    }
+    match serde_json::from_str::<serde_json::Value>(response.body()) {
+        Ok(serde_json::Value::Object(obj)) => {
+            !(obj.contains_key("Note") || obj.contains_key("Information") || obj.contains_key("Error Message"))
+        }
+        // CSV bodies are not JSON
+        _ => true,
+    }
+}
+
+/// Remove the `apikey` parameter from a URL and sort the remaining query
+/// parameters, so equivalent requests share a cache entry.
+pub(crate) fn strip_api_key(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let mut params: Vec<&str> = query
+        .split('&')
+        .filter(|p| !p.is_empty() && !p.starts_with("apikey="))
+        .collect();
+    params.sort_unstable();
+    format!("{base}?{}", params.join("&"))
+}
+
+pub(crate) fn query_param(url: &str, name: &str) -> Option<String> {
+    let (_, query) = url.split_once('?')?;
+    query
+        .split('&')
+        .filter_map(|p| p.split_once('='))
+        .find(|(k, _)| *k == name)
+        .map(|(_, v)| v.to_string())
+}
+
+/// FNV-1a hash of the canonical URL. Stable across Rust versions and platforms,
+/// unlike `std::hash::DefaultHasher`.
+fn fingerprint(url: &str) -> u64 {
+    strip_api_key(url).bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Matcher, MockClient, MockResponse};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("alphav-cache-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    const URL: &str = "https://www.alphavantage.co/query?function=TIME_SERIES_DAILY&symbol=IBM&apikey=secret";
+
+    #[test]
+    fn strip_api_key_removes_key_and_sorts_params() {
+        assert_eq!(
+            strip_api_key(URL),
+            "https://www.alphavantage.co/query?function=TIME_SERIES_DAILY&symbol=IBM"
+        );
+        assert_eq!(
+            fingerprint(URL),
+            fingerprint("https://www.alphavantage.co/query?apikey=other&symbol=IBM&function=TIME_SERIES_DAILY")
+        );
+    }
+
+    #[tokio::test]
+    async fn serves_second_request_from_disk() {
+        let dir = temp_dir("hit");
+        let inner = MockClient::default();
+        inner.on(Matcher::any(), MockResponse::ok(r#"{"Meta Data": {}}"#));
+        let client = CachedClient::wrap(inner.clone(), &dir);
+
+        let first = client.get(URL).await.unwrap();
+        let second = client.get(URL).await.unwrap();
+        assert_eq!(first.body(), second.body());
+        assert_eq!(inner.requests().len(), 1);
+
+        let entry = client.lookup(URL).unwrap().expect("entry should be stored");
+        assert_eq!(entry.function.as_deref(), Some("TIME_SERIES_DAILY"));
+        assert_eq!(entry.symbol.as_deref(), Some("IBM"));
+        assert!(!entry.url.contains("secret"));
+        assert!(
+            !std::fs::read_to_string(client.path_for(URL))
+                .unwrap()
+                .contains("secret")
+        );
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn offline_miss_is_an_error() {
+        let dir = temp_dir("miss");
+        let inner = MockClient::default();
+        let client = CachedClient::wrap(inner.clone(), &dir).offline(true);
+
+        let err = client.get(URL).await.unwrap_err();
+        assert!(matches!(err, Error::CacheMiss { .. }), "got: {err:?}");
+        assert!(!err.to_string().contains("secret"), "got: {err}");
+        assert!(inner.requests().is_empty());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn rate_limit_notes_are_not_cached() {
+        let dir = temp_dir("note");
+        let inner = MockClient::default();
+        inner.on(Matcher::any(), MockResponse::rate_limited());
+        let client = CachedClient::wrap(inner.clone(), &dir);
+
+        client.get(URL).await.unwrap();
+        client.get(URL).await.unwrap();
+        assert_eq!(inner.requests().len(), 2);
+        assert!(client.lookup(URL).unwrap().is_none());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
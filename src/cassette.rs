@@ -0,0 +1,412 @@
+//! Record/replay HTTP cassettes for deterministic tests
+//!
+//! [`Cassette`] wraps another [`Request`] implementation. In [`Mode::Record`] it
+//! forwards every request and writes the request/response pairs to a fixture file;
+//! in [`Mode::Replay`] it serves those pairs back without touching the network.
+//! API keys are removed from recorded URLs and request bodies, so fixtures can be
+//! committed.
+//!
+//! Recording merges into an existing fixture: a re-recorded request replaces its
+//! old interactions and everything else is kept, so several tests can share one
+//! cassette file.
+//!
+//! ```no_run
+//! use alphav::{AlphaVantage, rest};
+//! use alphav::cassette::{Cassette, Mode};
+//!
+//! # async fn example() -> alphav::Result<()> {
+//! // Record once with a real key (or set ALPHAVANTAGE_RECORD=1)...
+//! let client = AlphaVantage::default()
+//!     .with_key("my_api_key")
+//!     .wrap_client(|http| Cassette::wrap(http, "tests/fixtures/ibm_daily.json").mode(Mode::Record));
+//! rest::time_series::daily(&client, "IBM").get().await?;
+//!
+//! // ...then replay offline, with any key
+//! let client = AlphaVantage::default()
+//!     .with_key("replay")
+//!     .wrap_client(|http| Cassette::wrap(http, "tests/fixtures/ibm_daily.json"));
+//! let json = rest::time_series::daily(&client, "IBM").get().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::strip_api_key;
+use crate::error::{Error, Result};
+use crate::request::{HttpResponse, Request};
+use crate::response::Response;
+
+/// Environment variable switching cassettes to record mode (`1` or `true`).
+pub const RECORD_ENV: &str = "ALPHAVANTAGE_RECORD";
+
+/// Serializes fixture rewrites, so cassettes recording in parallel don't lose
+/// each other's interactions
+static FIXTURE_WRITES: Mutex<()> = Mutex::new(());
+
+/// Whether a cassette records or replays interactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Forward requests to the wrapped client and record them
+    Record,
+    /// Serve recorded responses only
+    Replay,
+}
+
+impl Mode {
+    /// [`Mode::Record`] if [`RECORD_ENV`] is set, otherwise [`Mode::Replay`].
+    pub fn from_env() -> Self {
+        match std::env::var(RECORD_ENV) {
+            Ok(v) if v == "1" || v.eq_ignore_ascii_case("true") => Mode::Record,
+            _ => Mode::Replay,
+        }
+    }
+}
+
+/// A recorded request/response pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// HTTP method (`GET` or `POST`)
+    pub method: String,
+    /// Request URL with the API key removed
+    pub url: String,
+    /// Request body for `POST` requests, with the API key removed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    /// HTTP status code
+    pub status: u16,
+    /// Request ID if available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Response body
+    pub body: String,
+}
+
+impl Interaction {
+    fn same_request(&self, other: &Interaction) -> bool {
+        self.method == other.method && self.url == other.url
+    }
+}
+
+/// Contents of a cassette fixture file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Tape {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Interactions recorded in this session, or loaded for replay
+    tape: Option<Tape>,
+    /// Number of times each (method, url) pair has been replayed
+    cursors: HashMap<(String, String), usize>,
+}
+
+/// HTTP client wrapper that records or replays interactions
+#[derive(Debug, Clone)]
+pub struct Cassette<C: Request> {
+    inner: C,
+    path: PathBuf,
+    mode: Mode,
+    state: Arc<Mutex<State>>,
+}
+
+impl<C: Request> Cassette<C> {
+    /// Wrap `inner` with the cassette stored at `path`.
+    ///
+    /// The mode is taken from [`Mode::from_env`]; override it with [`mode`](Self::mode).
+    pub fn wrap(inner: C, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+            mode: Mode::from_env(),
+            state: Arc::default(),
+        }
+    }
+
+    /// Sets the record/replay mode.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The cassette fixture path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Interactions recorded so far (record mode) or loaded from the fixture (replay mode).
+    pub fn interactions(&self) -> Result<Vec<Interaction>> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.tape(&mut state)?.interactions.clone())
+    }
+
+    /// Lazily initialize the tape: empty when recording, loaded from disk when replaying.
+    fn tape<'s>(&self, state: &'s mut State) -> Result<&'s mut Tape> {
+        if state.tape.is_none() {
+            let tape = match self.mode {
+                Mode::Record => Tape::default(),
+                Mode::Replay => self.load()?,
+            };
+            state.tape = Some(tape);
+        }
+        Ok(state.tape.get_or_insert_default())
+    }
+
+    /// Read the fixture file.
+    fn load(&self) -> Result<Tape> {
+        let contents = std::fs::read_to_string(&self.path)
+            .map_err(|e| Error::Custom(format!("Failed to read cassette {}: {e}", self.path.display())))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| Error::Custom(format!("Invalid cassette {}: {e}", self.path.display())))
+    }
+
+    /// Serve the next recorded response for this request. Repeated identical
+    /// requests are answered in recording order; the last match is reused once
+    /// the recorded ones are exhausted.
+    fn replay(&self, method: &str, url: &str) -> Result<HttpResponse> {
+        let url = strip_api_key(url);
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let matches: Vec<Interaction> = self
+            .tape(&mut state)?
+            .interactions
+            .iter()
+            .filter(|i| i.method == method && i.url == url)
+            .cloned()
+            .collect();
+
+        let cursor = state.cursors.entry((method.to_string(), url.clone())).or_default();
+        let interaction = matches.get(*cursor).or(matches.last()).ok_or_else(|| {
+            Error::Custom(format!(
+                "No recorded interaction for {method} {url} in cassette {}",
+                self.path.display()
+            ))
+        })?;
+        *cursor += 1;
+
+        Ok(HttpResponse::new(
+            interaction.status,
+            interaction.body.clone(),
+            interaction.request_id.clone(),
+        ))
+    }
+
+    /// Append an interaction and merge it into the fixture file.
+    ///
+    /// The first interaction recorded this session for a request replaces the ones
+    /// already in the file for it; interactions for other requests are kept.
+    fn record(&self, interaction: Interaction) -> Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let session = self.tape(&mut state)?;
+        let rerecorded = session.interactions.iter().any(|i| i.same_request(&interaction));
+        session.interactions.push(interaction.clone());
+
+        let _guard = FIXTURE_WRITES.lock().unwrap_or_else(|e| e.into_inner());
+        let mut tape = if self.path.exists() {
+            self.load()?
+        } else {
+            Tape::default()
+        };
+        if !rerecorded {
+            tape.interactions.retain(|i| !i.same_request(&interaction));
+        }
+        tape.interactions.push(interaction);
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| Error::Custom(format!("Failed to create cassette directory: {e}")))?;
+        }
+        let contents = serde_json::to_string_pretty(&tape)?;
+        std::fs::write(&self.path, contents)
+            .map_err(|e| Error::Custom(format!("Failed to write cassette {}: {e}", self.path.display())))
+    }
+
+    fn finish<R: Response>(
+        &self,
+        method: &str,
+        url: &str,
+        request_body: Option<&str>,
+        response: R,
+    ) -> Result<HttpResponse> {
+        let interaction = Interaction {
+            method: method.to_string(),
+            url: strip_api_key(url),
+            request_body: request_body.map(strip_body_key),
+            status: response.status(),
+            request_id: response.request_id().clone(),
+            body: response.body().to_owned(),
+        };
+        self.record(interaction.clone())?;
+        Ok(HttpResponse::new(
+            interaction.status,
+            interaction.body,
+            interaction.request_id,
+        ))
+    }
+}
+
+/// Remove the API key from a request body: the `apikey` field of a JSON object,
+/// or the `apikey` parameter of a form-encoded body.
+fn strip_body_key(body: &str) -> String {
+    if let Ok(serde_json::Value::Object(mut object)) = serde_json::from_str(body) {
+        object.remove("apikey");
+        return serde_json::Value::Object(object).to_string();
+    }
+    body.split('&')
+        .filter(|p| !p.starts_with("apikey="))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+impl<C: Request> Request for Cassette<C> {
+    type Response = HttpResponse;
+
+    /// Wrap a default HTTP client with the cassette at `tests/fixtures/cassette.json`.
+    fn new() -> Self {
+        Self::wrap(C::new(), "tests/fixtures/cassette.json")
+    }
+
+    async fn get(&self, url: &str) -> Result<Self::Response> {
+        match self.mode {
+            Mode::Replay => self.replay("GET", url),
+            Mode::Record => {
+                let response = self.inner.get(url).await?;
+                self.finish("GET", url, None, response)
+            }
+        }
+    }
+
+    async fn post(&self, url: &str, body: &str) -> Result<Self::Response> {
+        match self.mode {
+            Mode::Replay => self.replay("POST", url),
+            Mode::Record => {
+                let response = self.inner.post(url, body).await?;
+                self.finish("POST", url, Some(body), response)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Matcher, MockClient, MockResponse};
+
+    fn mock() -> MockClient {
+        let mock = MockClient::default();
+        mock.on(Matcher::any(), MockResponse::ok(r#"{"Symbol": "IBM"}"#));
+        mock
+    }
+
+    const URL: &str = "https://www.alphavantage.co/query?function=OVERVIEW&symbol=IBM&apikey=secret";
+
+    #[tokio::test]
+    async fn records_then_replays_without_api_key() {
+        let path = std::env::temp_dir().join(format!("alphav-cassette-{}.json", std::process::id()));
+
+        let recorder = Cassette::wrap(mock(), &path).mode(Mode::Record);
+        let recorded = recorder.get(URL).await.unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("apikey=secret"));
+
+        let player = Cassette::wrap(mock(), &path).mode(Mode::Replay);
+        let replayed = player
+            .get("https://www.alphavantage.co/query?function=OVERVIEW&symbol=IBM&apikey=other")
+            .await
+            .unwrap();
+        assert_eq!(recorded.body(), replayed.body());
+
+        let err = player
+            .get("https://www.alphavantage.co/query?function=OVERVIEW&symbol=AAPL&apikey=other")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No recorded interaction"), "got: {err}");
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    async fn records_post_bodies_without_api_key() {
+        let path = std::env::temp_dir().join(format!("alphav-cassette-post-{}.json", std::process::id()));
+
+        let recorder = Cassette::wrap(mock(), &path).mode(Mode::Record);
+        recorder
+            .post(URL, "function=OVERVIEW&apikey=secret&symbol=IBM")
+            .await
+            .unwrap();
+        recorder
+            .post(URL, r#"{"apikey": "secret", "symbol": "IBM"}"#)
+            .await
+            .unwrap();
+
+        let fixture = std::fs::read_to_string(&path).unwrap();
+        assert!(!fixture.contains("secret"), "got: {fixture}");
+        let bodies: Vec<Option<String>> = recorder
+            .interactions()
+            .unwrap()
+            .into_iter()
+            .map(|i| i.request_body)
+            .collect();
+        assert_eq!(
+            bodies,
+            [
+                Some("function=OVERVIEW&symbol=IBM".to_string()),
+                Some(r#"{"symbol":"IBM"}"#.to_string())
+            ]
+        );
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    async fn recording_merges_into_existing_cassette() {
+        let path = std::env::temp_dir().join(format!("alphav-cassette-merge-{}.json", std::process::id()));
+        let aapl = URL.replace("IBM", "AAPL");
+
+        let first = Cassette::wrap(mock(), &path).mode(Mode::Record);
+        first.get(URL).await.unwrap();
+        first.get(URL).await.unwrap();
+        let second = Cassette::wrap(mock(), &path).mode(Mode::Record);
+        second.get(&aapl).await.unwrap();
+        // Re-recording a request replaces its interactions from earlier sessions
+        let third = Cassette::wrap(mock(), &path).mode(Mode::Record);
+        third.get(URL).await.unwrap();
+
+        let player = Cassette::wrap(mock(), &path).mode(Mode::Replay);
+        let urls: Vec<String> = player.interactions().unwrap().into_iter().map(|i| i.url).collect();
+        assert_eq!(urls, [strip_api_key(&aapl), strip_api_key(URL)]);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn replays_repeated_requests_in_order() {
+        let path = std::env::temp_dir().join(format!("alphav-cassette-order-{}.json", std::process::id()));
+        let url = strip_api_key(URL);
+        let tape = Tape {
+            interactions: ["first", "second"]
+                .into_iter()
+                .map(|body| Interaction {
+                    method: "GET".to_string(),
+                    url: url.clone(),
+                    request_body: None,
+                    status: 200,
+                    request_id: None,
+                    body: body.to_string(),
+                })
+                .collect(),
+        };
+        std::fs::write(&path, serde_json::to_string(&tape).unwrap()).unwrap();
+
+        let player = Cassette::wrap(mock(), &path).mode(Mode::Replay);
+        let bodies: Vec<String> = (0..3)
+            .map(|_| player.replay("GET", URL).unwrap().body().to_owned())
+            .collect();
+        assert_eq!(bodies, ["first", "second", "second"]);
+
+        std::fs::remove_file(path).ok();
+    }
+}
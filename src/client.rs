@@ -1,5 +1,15 @@
 //! Main Alpha Vantage API client
+use std::sync::Arc;
+
+use crate::credentials::CredentialProvider;
+use crate::error::{Error, Result};
+use crate::key_pool::{self, KeyPool};
 use crate::request::Request;
+use crate::response::Response;
+use crate::secret::ApiKey;
+
+/// Default Alpha Vantage API host. Requests are sent to `{base_url}/query`.
+pub const DEFAULT_BASE_URL: &str = "https://www.alphavantage.co";
 
 /// The main Alpha Vantage API client.
 ///
@@ -10,7 +20,8 @@ use crate::request::Request;
 #[derive(Debug, Clone)]
 pub struct AlphaVantage<Client: Request = reqwest::Client> {
     client: Client,
-    api_key: Option<String>,
+    credentials: Option<Arc<dyn CredentialProvider>>,
+    base_url: String,
 }
 
 /// The main Alpha Vantage API client.
@@ -22,7 +33,8 @@ pub struct AlphaVantage<Client: Request = reqwest::Client> {
 #[derive(Debug, Clone)]
 pub struct AlphaVantage<Client: Request = crate::request::HyperClient> {
     client: Client,
-    api_key: Option<String>,
+    credentials: Option<Arc<dyn CredentialProvider>>,
+    base_url: String,
 }
 
 /// The main Alpha Vantage API client.
@@ -34,7 +46,8 @@ pub struct AlphaVantage<Client: Request = crate::request::HyperClient> {
 #[derive(Debug, Clone)]
 pub struct AlphaVantage<Client: Request> {
     client: Client,
-    api_key: Option<String>,
+    credentials: Option<Arc<dyn CredentialProvider>>,
+    base_url: String,
 }
 
 // Implementation for any Client that implements Request
@@ -48,14 +61,15 @@ impl<Client: Request> AlphaVantage<Client> {
     ///
     /// Returns an error if the environment variable cannot be loaded or if the API key is missing.
     #[cfg(feature = "dotenvy")]
-    pub fn new() -> crate::Result<Self> {
+    pub fn new() -> Result<Self> {
         dotenvy::dotenv().ok(); // Try to load .env file, ignore errors
 
-        let api_key = std::env::var("ALPHAVANTAGE_API_KEY").map_err(|_| crate::Error::MissingApiKey)?;
+        let api_key = std::env::var("ALPHAVANTAGE_API_KEY").map_err(|_| Error::MissingApiKey)?;
 
         Ok(Self {
             client: Client::new(),
-            api_key: Some(api_key),
+            credentials: Some(Arc::new(ApiKey::new(api_key))),
+            base_url: DEFAULT_BASE_URL.to_string(),
         })
     }
 
@@ -66,7 +80,8 @@ impl<Client: Request> AlphaVantage<Client> {
     pub fn new() -> Self {
         Self {
             client: Client::new(),
-            api_key: None,
+            credentials: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
         }
     }
 
@@ -76,6 +91,26 @@ impl<Client: Request> AlphaVantage<Client> {
         self
     }
 
+    /// Wrap the HTTP client in another [`Request`] implementation, keeping the API key.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use alphav::AlphaVantage;
+    /// use alphav::cache::CachedClient;
+    ///
+    /// let client = AlphaVantage::default()
+    ///     .with_key("my_api_key")
+    ///     .wrap_client(|http| CachedClient::wrap(http, ".alphav-cache"));
+    /// ```
+    pub fn wrap_client<Wrapped: Request>(self, wrap: impl FnOnce(Client) -> Wrapped) -> AlphaVantage<Wrapped> {
+        AlphaVantage {
+            client: wrap(self.client),
+            credentials: self.credentials,
+            base_url: self.base_url,
+        }
+    }
+
     /// Set the API key for this instance.
     ///
     /// # Examples
@@ -85,46 +120,117 @@ impl<Client: Request> AlphaVantage<Client> {
     ///
     /// let client = AlphaVantage::default().with_key("my_api_key");
     /// ```
-    pub fn with_key(mut self, api_key: impl Into<String>) -> Self {
-        self.api_key = Some(api_key.into());
+    pub fn with_key(mut self, api_key: impl Into<ApiKey>) -> Self {
+        self.credentials = Some(Arc::new(api_key.into()));
+        self
+    }
+
+    /// Use a pool of API keys instead of a single key.
+    ///
+    /// Each request takes a key from the pool. When Alpha Vantage answers with a
+    /// rate-limit note, the key is benched and the request is retried with another
+    /// key. Replaces any key set with [`with_key`](Self::with_key).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use alphav::AlphaVantage;
+    /// use alphav::key_pool::{KeyPool, Strategy};
+    ///
+    /// let client = AlphaVantage::default()
+    ///     .with_key_pool(KeyPool::new(["key_a", "key_b"]).strategy(Strategy::RoundRobin));
+    /// ```
+    pub fn with_key_pool(self, pool: KeyPool) -> Self {
+        self.with_credentials(pool)
+    }
+
+    /// Resolve the API key through `provider` on every request.
+    ///
+    /// See [`credentials`](crate::credentials) for the available providers.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use alphav::AlphaVantage;
+    /// use alphav::credentials::EnvKey;
+    ///
+    /// let client = AlphaVantage::default().with_credentials(EnvKey::default());
+    /// ```
+    pub fn with_credentials(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.credentials = Some(Arc::new(provider));
+        self
+    }
+
+    /// Set the API host for this instance, e.g. a local fake server for tests.
+    ///
+    /// Requests are sent to `{base_url}/query`. Defaults to [`DEFAULT_BASE_URL`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use alphav::AlphaVantage;
+    ///
+    /// let client = AlphaVantage::default()
+    ///     .with_key("test")
+    ///     .with_base_url("http://127.0.0.1:8080");
+    /// ```
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
         self
     }
 
-    /// Get the API key for this instance.
+    /// Get the API host for this instance.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Get the API key set with [`with_key`](Self::with_key).
+    ///
+    /// Returns `None` for providers that resolve keys per request.
     pub fn api_key(&self) -> Option<&str> {
-        self.api_key.as_deref()
+        self.credentials.as_ref()?.static_key().map(ApiKey::expose)
+    }
+
+    /// Get the credential provider for this instance.
+    pub fn credentials(&self) -> Option<&dyn CredentialProvider> {
+        self.credentials.as_deref()
     }
 
     /// Get a reference to the underlying HTTP client.
     pub fn client(&self) -> &Client {
         &self.client
     }
-}
 
-// Default implementation for reqwest
-#[cfg(feature = "reqwest")]
-impl Default for AlphaVantage<reqwest::Client> {
-    fn default() -> Self {
-        Self {
-            client: reqwest::Client::new(),
-            api_key: None,
+    /// Send a `/query` request with `params`, appending the API key.
+    ///
+    /// When a rate-limited response comes back, the credential provider decides
+    /// whether to retry with another key (a [`KeyPool`] does until it runs dry).
+    pub(crate) async fn query(&self, params: &[String]) -> Result<Client::Response> {
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or_else(|| Error::Custom("API key not set".to_string()))?;
+
+        loop {
+            let api_key = credentials.api_key()?;
+            let response = self.client.get(&self.query_url(params, &api_key)).await?;
+            match key_pool::rate_limit(response.body()) {
+                Some(limit) if credentials.rate_limited(&api_key, limit) => continue,
+                _ => return Ok(response),
+            }
         }
     }
-}
 
-// Default implementation for hyper
-#[cfg(all(feature = "hyper", not(feature = "reqwest")))]
-impl Default for AlphaVantage<crate::request::HyperClient> {
-    fn default() -> Self {
-        Self {
-            client: crate::request::HyperClient::new(),
-            api_key: None,
-        }
+    fn query_url(&self, params: &[String], api_key: &ApiKey) -> String {
+        format!(
+            "{}/query?{}&apikey={}",
+            self.base_url,
+            params.join("&"),
+            api_key.expose()
+        )
     }
 }
 
-// Default implementation when no HTTP client feature is enabled
-#[cfg(not(any(feature = "reqwest", feature = "hyper")))]
 impl<Client: Request> Default for AlphaVantage<Client> {
     /// Create a default Alpha Vantage client with no API key set.
     ///
@@ -132,7 +238,59 @@ impl<Client: Request> Default for AlphaVantage<Client> {
     fn default() -> Self {
         Self {
             client: Client::new(),
-            api_key: None,
+            credentials: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
         }
     }
 }
+
+/// Client handle held by request builders
+///
+/// Builders made from an `Arc<AlphaVantage>`, such as the one returned by
+/// [`instance`](crate::instance), own their client: they are `'static` and their
+/// futures can be passed to `tokio::spawn`. Builders made from a reference
+/// borrow it, as before.
+pub enum ClientRef<'a, Client: Request> {
+    /// Borrowed client
+    Borrowed(&'a AlphaVantage<Client>),
+    /// Shared client
+    Shared(Arc<AlphaVantage<Client>>),
+}
+
+impl<Client: Request> Clone for ClientRef<'_, Client> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Borrowed(client) => Self::Borrowed(client),
+            Self::Shared(client) => Self::Shared(Arc::clone(client)),
+        }
+    }
+}
+
+impl<Client: Request> std::ops::Deref for ClientRef<'_, Client> {
+    type Target = AlphaVantage<Client>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(client) => client,
+            Self::Shared(client) => client,
+        }
+    }
+}
+
+impl<'a, Client: Request> From<&'a AlphaVantage<Client>> for ClientRef<'a, Client> {
+    fn from(client: &'a AlphaVantage<Client>) -> Self {
+        Self::Borrowed(client)
+    }
+}
+
+impl<'a, Client: Request> From<&'a Arc<AlphaVantage<Client>>> for ClientRef<'a, Client> {
+    fn from(client: &'a Arc<AlphaVantage<Client>>) -> Self {
+        Self::Borrowed(client)
+    }
+}
+
+impl<Client: Request> From<Arc<AlphaVantage<Client>>> for ClientRef<'static, Client> {
+    fn from(client: Arc<AlphaVantage<Client>>) -> Self {
+        Self::Shared(client)
+    }
+}
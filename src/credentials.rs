@@ -0,0 +1,227 @@
+//! Sources of API keys
+//!
+//! The client asks its [`CredentialProvider`] for a key on every request, so
+//! keys can change without rebuilding the client or calling
+//! [`initialize`](crate::initialize) again.
+//!
+//! | Provider | Key comes from |
+//! |---|---|
+//! | [`ApiKey`] | a fixed value, as set by [`with_key`](crate::client::AlphaVantage::with_key) |
+//! | [`EnvKey`] | an environment variable, read on every request |
+//! | [`FileKey`] | a file, re-read whenever its modification time changes |
+//! | [`from_fn`] | a user callback |
+//! | [`KeyPool`](crate::key_pool::KeyPool) | a rotating set of keys |
+//!
+//! ```no_run
+//! use alphav::AlphaVantage;
+//! use alphav::credentials::FileKey;
+//!
+//! // Kubernetes-style mounted secret, rotated in place
+//! let client = AlphaVantage::default().with_credentials(FileKey::new("/var/run/secrets/alphavantage/api-key"));
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::error::{Error, Result};
+use crate::key_pool::{KeyPool, RateLimit};
+use crate::secret::ApiKey;
+
+/// Environment variable holding the API key
+pub const API_KEY_ENV: &str = "ALPHAVANTAGE_API_KEY";
+
+/// Supplies the API key for each request
+pub trait CredentialProvider: Send + Sync + fmt::Debug {
+    /// Key to use for the next request.
+    fn api_key(&self) -> Result<ApiKey>;
+
+    /// Called when Alpha Vantage answers a request made with `key` with a rate-limit
+    /// note. Return `true` if the request should be retried with a fresh
+    /// [`api_key`](Self::api_key); only do so if that can yield a different key.
+    fn rate_limited(&self, key: &ApiKey, limit: RateLimit) -> bool {
+        let _ = (key, limit);
+        false
+    }
+
+    /// The fixed key behind this provider, if there is one.
+    fn static_key(&self) -> Option<&ApiKey> {
+        None
+    }
+}
+
+impl CredentialProvider for ApiKey {
+    fn api_key(&self) -> Result<ApiKey> {
+        Ok(self.clone())
+    }
+
+    fn static_key(&self) -> Option<&ApiKey> {
+        Some(self)
+    }
+}
+
+impl CredentialProvider for KeyPool {
+    fn api_key(&self) -> Result<ApiKey> {
+        self.acquire()
+    }
+
+    fn rate_limited(&self, key: &ApiKey, limit: RateLimit) -> bool {
+        self.mark_limited(key, limit);
+        true
+    }
+}
+
+/// Reads the key from an environment variable on every request
+#[derive(Debug, Clone)]
+pub struct EnvKey {
+    var: String,
+}
+
+impl EnvKey {
+    /// Read the key from `var`.
+    pub fn new(var: impl Into<String>) -> Self {
+        Self { var: var.into() }
+    }
+}
+
+impl Default for EnvKey {
+    /// Read the key from [`API_KEY_ENV`].
+    fn default() -> Self {
+        Self::new(API_KEY_ENV)
+    }
+}
+
+impl CredentialProvider for EnvKey {
+    fn api_key(&self) -> Result<ApiKey> {
+        match std::env::var(&self.var) {
+            Ok(key) if !key.trim().is_empty() => Ok(ApiKey::new(key.trim())),
+            _ => Err(Error::MissingApiKey),
+        }
+    }
+}
+
+/// Reads the key from a file, re-reading it when the file's modification time changes
+///
+/// Surrounding whitespace is trimmed. If the file briefly disappears or cannot be
+/// read while a secret is being rotated, the last key read is used.
+#[derive(Debug)]
+pub struct FileKey {
+    path: PathBuf,
+    cached: Mutex<Option<(SystemTime, ApiKey)>>,
+}
+
+impl FileKey {
+    /// Read the key from `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Path of the key file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read(&self) -> std::io::Result<(SystemTime, String)> {
+        let modified = std::fs::metadata(&self.path)?.modified()?;
+        let contents = std::fs::read_to_string(&self.path)?;
+        Ok((modified, contents))
+    }
+}
+
+impl CredentialProvider for FileKey {
+    fn api_key(&self) -> Result<ApiKey> {
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+
+        let modified = std::fs::metadata(&self.path).and_then(|m| m.modified());
+        if let (Ok(modified), Some((seen, key))) = (&modified, cached.as_ref())
+            && modified == seen
+        {
+            return Ok(key.clone());
+        }
+
+        match self.read() {
+            Ok((modified, contents)) if !contents.trim().is_empty() => {
+                let key = ApiKey::new(contents.trim());
+                *cached = Some((modified, key.clone()));
+                Ok(key)
+            }
+            Ok(_) => Err(Error::MissingApiKey),
+            Err(e) => match cached.as_ref() {
+                Some((_, key)) => Ok(key.clone()),
+                None => Err(Error::Custom(format!(
+                    "Failed to read API key from {}: {e}",
+                    self.path.display()
+                ))),
+            },
+        }
+    }
+}
+
+/// Provider calling a closure for each key, created with [`from_fn`]
+pub struct FromFn<F>(F);
+
+impl<F> fmt::Debug for FromFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FromFn").finish_non_exhaustive()
+    }
+}
+
+impl<F: Fn() -> Result<ApiKey> + Send + Sync> CredentialProvider for FromFn<F> {
+    fn api_key(&self) -> Result<ApiKey> {
+        (self.0)()
+    }
+}
+
+/// Provide keys from a callback, e.g. one reading a secrets manager cache.
+///
+/// ```
+/// use alphav::{AlphaVantage, ApiKey};
+/// use alphav::credentials;
+///
+/// let client = AlphaVantage::default().with_credentials(credentials::from_fn(|| Ok(ApiKey::new("from_vault"))));
+/// ```
+pub fn from_fn<F: Fn() -> Result<ApiKey> + Send + Sync>(f: F) -> FromFn<F> {
+    FromFn(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn file_key_rereads_on_change() {
+        let path = std::env::temp_dir().join(format!("alphav-key-{}", std::process::id()));
+        std::fs::write(&path, "first\n").unwrap();
+        let provider = FileKey::new(&path);
+        assert_eq!(provider.api_key().unwrap().expose(), "first");
+
+        std::fs::write(&path, "second").unwrap();
+        // Filesystems with coarse timestamps may not have moved the mtime yet
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+        assert_eq!(provider.api_key().unwrap().expose(), "second");
+
+        // Keep serving the last key while the file is missing
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(provider.api_key().unwrap().expose(), "second");
+        assert!(FileKey::new(&path).api_key().is_err());
+    }
+
+    #[test]
+    fn env_and_callback_providers() {
+        assert!(matches!(
+            EnvKey::new("ALPHAV_TEST_UNSET_KEY").api_key(),
+            Err(Error::MissingApiKey)
+        ));
+
+        let provider = from_fn(|| Ok(ApiKey::new("callback")));
+        assert_eq!(provider.api_key().unwrap().expose(), "callback");
+        assert!(provider.static_key().is_none());
+        assert!(!provider.rate_limited(&ApiKey::new("callback"), RateLimit::PerMinute));
+    }
+}
@@ -25,6 +25,18 @@ pub enum Error {
         /// Request ID if available
         request_id: Option<String>,
     },
+    /// No cached response is available and the client is in offline mode
+    CacheMiss {
+        /// Request URL with the API key removed
+        url: String,
+    },
+    /// The request did not complete within the configured time
+    Timeout(std::time::Duration),
+    /// Every API key in the pool is out of quota
+    RateLimited {
+        /// Time until the first key becomes usable again
+        retry_after: std::time::Duration,
+    },
     /// Custom error message
     Custom(String),
 }
@@ -49,6 +61,15 @@ impl fmt::Display for Error {
                 }
                 Ok(())
             }
+            Error::CacheMiss { url } => write!(f, "No cached response for {url} (offline mode)"),
+            Error::Timeout(limit) => write!(f, "Request timed out after {limit:?}"),
+            Error::RateLimited { retry_after } => {
+                write!(
+                    f,
+                    "All API keys are rate limited; retry after {}s",
+                    retry_after.as_secs().max(1)
+                )
+            }
             Error::Custom(s) => write!(f, "{s}"),
         }
     }
@@ -68,8 +89,9 @@ impl StdError for Error {
 
 #[cfg(feature = "reqwest")]
 impl From<reqwest::Error> for Error {
+    /// The request URL is dropped from the error because it carries the API key.
     fn from(e: reqwest::Error) -> Self {
-        Error::Reqwest(std::sync::Arc::new(e))
+        Error::Reqwest(std::sync::Arc::new(e.without_url()))
     }
 }
 
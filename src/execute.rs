@@ -1,9 +1,12 @@
 //! Execute trait for running API requests
 //!
 //! The `Execute` trait provides the `.get()` method used by all endpoint builders
-//! to execute requests and return results.
+//! to execute requests and return results, and [`timeout`](Execute::timeout)
+//! to bound how long that may take.
 
-use crate::error::Result;
+use std::time::Duration;
+
+use crate::error::{Error, Result};
 
 /// Trait for executing API requests
 ///
@@ -14,5 +17,149 @@ pub trait Execute {
     type Output;
 
     /// Execute the request and return the result
-    fn get(self) -> impl std::future::Future<Output = Result<Self::Output>>;
+    ///
+    /// The future is `Send`, and `'static` when the builder is, e.g. when it was
+    /// made from an `Arc<AlphaVantage>`, so it can be passed to `tokio::spawn`.
+    fn get(self) -> impl std::future::Future<Output = Result<Self::Output>> + Send;
+
+    /// Fail with [`Error::Timeout`] if the request, including reading and
+    /// processing the response, takes longer than `limit`.
+    ///
+    /// Call it after the other builder methods. Dropping the future returned by
+    /// `get` cancels the request at any point.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use alphav::AlphaVantage;
+    /// use alphav::execute::Execute;
+    /// use alphav::rest;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> alphav::Result<()> {
+    /// let client = AlphaVantage::default().with_key("my_api_key");
+    /// let json = rest::time_series::daily(&client, "IBM")
+    ///     .timeout(Duration::from_secs(2))
+    ///     .get()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn timeout(self, limit: Duration) -> Timeout<Self>
+    where
+        Self: Sized,
+    {
+        Timeout { inner: self, limit }
+    }
+}
+
+/// A request bounded by a time limit, created by [`Execute::timeout`]
+pub struct Timeout<E> {
+    inner: E,
+    limit: Duration,
+}
+
+impl<E: Execute> Timeout<E> {
+    /// Execute the request and return the result
+    pub fn get(self) -> impl std::future::Future<Output = Result<E::Output>> + Send {
+        Execute::get(self)
+    }
+}
+
+impl<E: Execute> Execute for Timeout<E> {
+    type Output = E::Output;
+
+    fn get(self) -> impl std::future::Future<Output = Result<E::Output>> + Send {
+        // Start the inner request here so `E` itself need not be `Send`
+        let Timeout { inner, limit } = self;
+        let request = inner.get();
+        async move {
+            tokio::time::timeout(limit, request)
+                .await
+                .map_err(|_| Error::Timeout(limit))?
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::client::AlphaVantage;
+    use crate::mock::{Matcher, MockClient, MockResponse};
+    use crate::rest;
+    use crate::tool_use::call_tool_with_timeout;
+
+    /// A client that never answers in time
+    fn slow() -> AlphaVantage<MockClient> {
+        let mock = MockClient::default();
+        mock.on(Matcher::any(), MockResponse::ok("{}").delay(Duration::from_secs(10)));
+        AlphaVantage::default().with_client(mock).with_key("test")
+    }
+
+    #[tokio::test]
+    async fn timeout_bounds_builders_and_tool_calls() {
+        let client = slow();
+        let limit = Duration::from_millis(20);
+
+        let err = rest::time_series::daily(&client, "IBM")
+            .timeout(limit)
+            .get()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Timeout(l) if l == limit), "got: {err:?}");
+
+        let request = serde_json::json!({ "tool": "company_overview", "params": { "symbol": "IBM" } });
+        let err = call_tool_with_timeout(&client, request, limit).await.unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)), "got: {err:?}");
+    }
+
+    /// Compile-time check that a value can be moved into `tokio::spawn`
+    fn assert_spawnable<T: Send + 'static>(_: &T) {}
+
+    /// Compile-time check that `Execute` futures are `Send` for any builder
+    fn send_future<E: Execute>(request: E) -> impl std::future::Future<Output = Result<E::Output>> + Send {
+        request.get()
+    }
+
+    #[tokio::test]
+    async fn owned_builders_can_be_spawned() {
+        let client = Arc::new(slow());
+        let limit = Duration::from_millis(20);
+
+        assert_spawnable(&rest::fundamentals::earnings(client.clone(), "IBM"));
+        assert_spawnable(&rest::quotes::realtime_bulk(client.clone(), ["IBM", "AAPL"]).get());
+        assert_spawnable(&send_future(rest::time_series::weekly(client.clone(), "IBM")));
+
+        let request = rest::time_series::daily(client, "IBM").timeout(limit).get();
+        let err = tokio::spawn(request).await.unwrap().unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)), "got: {err:?}");
+    }
+
+    #[test]
+    fn wrapped_and_alternative_clients_are_spawnable() {
+        let cassette = AlphaVantage::default()
+            .with_client(crate::cassette::Cassette::wrap(MockClient::default(), "unused.json"))
+            .with_key("test");
+        assert_spawnable(&rest::time_series::daily(Arc::new(cassette), "IBM").get());
+
+        let cached = AlphaVantage::default()
+            .with_client(crate::cache::CachedClient::wrap(MockClient::default(), "unused"))
+            .with_key("test");
+        assert_spawnable(&rest::time_series::daily(Arc::new(cached), "IBM").get());
+
+        #[cfg(feature = "reqwest")]
+        {
+            let reqwest = AlphaVantage::<reqwest::Client>::default().with_key("test");
+            assert_spawnable(&rest::time_series::daily(Arc::new(reqwest), "IBM").get());
+        }
+    }
+
+    #[cfg(any(feature = "hyper", feature = "reqwest"))]
+    #[test]
+    fn static_instance_builders_are_static() {
+        assert_spawnable(&rest::time_series::daily(crate::instance(), "IBM").get());
+    }
 }
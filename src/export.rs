@@ -0,0 +1,412 @@
+//! Parquet, Arrow IPC and CSV export with Alpha Vantage provenance
+//!
+//! Files carry where their data came from: the API function, symbol, fetch time
+//! and the response's last refreshed time. Parquet stores these as key-value
+//! metadata and Arrow IPC as custom schema metadata. CSV has no place for them,
+//! so [`write_file`] puts them in a JSON sidecar next to the file, named by
+//! [`csv_metadata_path`], and leaves the CSV itself plain.
+//!
+//! Builders' `as_dataset()` return a table together with its [`Provenance`],
+//! stamped with the time the response arrived:
+//!
+//! ```no_run
+//! use alphav::AlphaVantage;
+//! use alphav::export;
+//! use alphav::rest;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = AlphaVantage::default().with_key("your_api_key");
+//! let mut bars = rest::time_series::daily(&client, "IBM").as_dataset().get().await?;
+//! export::write_file(&mut bars.data, "ibm_daily.parquet", &bars.provenance)?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::error::{Error, Result};
+use crate::processor::table::typed_dataframe;
+use crate::processor::{Processor, TypedTable};
+use crate::response::Response;
+use crate::tool_use::ToolResult;
+use chrono::{DateTime, SecondsFormat, Utc};
+use polars_core::frame::DataFrame;
+use polars_core::prelude::PlSmallStr;
+use polars_io::prelude::*;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Where an exported dataset came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    /// The Alpha Vantage `function`, e.g. `TIME_SERIES_DAILY`
+    pub function: String,
+    /// The requested symbol
+    pub symbol: Option<String>,
+    /// When the data was fetched
+    pub fetched_at: DateTime<Utc>,
+    /// The response's `Last Refreshed` time, as Alpha Vantage wrote it
+    pub last_refreshed: Option<String>,
+}
+
+impl Provenance {
+    /// Provenance for data from `function`, fetched now
+    pub fn new(function: impl Into<String>) -> Self {
+        Self {
+            function: function.into(),
+            symbol: None,
+            fetched_at: std::time::SystemTime::now().into(),
+            last_refreshed: None,
+        }
+    }
+
+    /// Provenance for `function`, taking the symbol and last refreshed time from a
+    /// response's metadata, such as its `Meta Data` object. The fetch time comes
+    /// from the `fetched_at` entry [`call_tool`](crate::tool_use::call_tool) adds,
+    /// or is now.
+    pub fn from_metadata(function: impl Into<String>, metadata: &Value) -> Self {
+        let field = |name: &str| {
+            metadata
+                .as_object()?
+                .iter()
+                .find(|(key, _)| key.to_lowercase().ends_with(name))
+                .and_then(|(_, value)| value.as_str())
+        };
+        let mut provenance = Self::new(function);
+        provenance.symbol = field("symbol").map(str::to_string);
+        provenance.last_refreshed = field("last refreshed").map(str::to_string);
+        if let Some(fetched_at) = field("fetched_at").and_then(|at| DateTime::parse_from_rfc3339(at).ok()) {
+            provenance.fetched_at = fetched_at.to_utc();
+        }
+        provenance
+    }
+
+    /// Set the symbol
+    pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Set when the data was fetched
+    pub fn fetched_at(mut self, fetched_at: DateTime<Utc>) -> Self {
+        self.fetched_at = fetched_at;
+        self
+    }
+
+    /// Set the response's last refreshed time
+    pub fn last_refreshed(mut self, last_refreshed: impl Into<String>) -> Self {
+        self.last_refreshed = Some(last_refreshed.into());
+        self
+    }
+
+    /// The metadata entries written to files, keyed `alphav.function`, `alphav.symbol`,
+    /// `alphav.fetched_at` (RFC 3339) and `alphav.last_refreshed`
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries = vec![("alphav.function".to_string(), self.function.clone())];
+        if let Some(symbol) = &self.symbol {
+            entries.push(("alphav.symbol".to_string(), symbol.clone()));
+        }
+        entries.push((
+            "alphav.fetched_at".to_string(),
+            self.fetched_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        ));
+        if let Some(last_refreshed) = &self.last_refreshed {
+            entries.push(("alphav.last_refreshed".to_string(), last_refreshed.clone()));
+        }
+        entries
+    }
+}
+
+/// A table and where its data came from, returned by builders' `as_dataset()`
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    /// The table, as `as_dataframe()` shapes it
+    pub data: DataFrame,
+    /// The request's function and symbol, the response's last refreshed time and
+    /// when it arrived
+    pub provenance: Provenance,
+}
+
+/// Processor that keeps the provenance a [`TypedTable`] drops
+pub struct Export {
+    table: TypedTable,
+    function: &'static str,
+    symbol: Option<String>,
+}
+
+impl Export {
+    /// Wrap a builder's table processor for its `function` and requested `symbol`
+    pub(crate) fn new(table: TypedTable, function: &'static str, symbol: Option<String>) -> Self {
+        Self {
+            table,
+            function,
+            symbol,
+        }
+    }
+}
+
+impl Processor for Export {
+    type Output = Dataset;
+
+    fn process<R: Response>(&self, response: Result<R>) -> Result<Dataset> {
+        // Processors run as soon as the response arrives. chrono is built without
+        // its `clock` feature, so read the time from std
+        let fetched_at = DateTime::<Utc>::from(std::time::SystemTime::now());
+        let (data, metadata) = self.table.table(response)?;
+        let mut provenance = match &metadata {
+            Some(metadata) => Provenance::from_metadata(self.function, metadata),
+            None => Provenance::new(self.function),
+        }
+        .fetched_at(fetched_at);
+        if provenance.symbol.is_none() {
+            provenance.symbol = self.symbol.clone();
+        }
+        Ok(Dataset { data, provenance })
+    }
+}
+
+/// File format to export to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Apache Parquet
+    Parquet,
+    /// Arrow IPC file (Feather v2)
+    Ipc,
+    /// Comma-separated values
+    Csv,
+}
+
+impl Format {
+    /// The format for a path's extension: `parquet`, `arrow`/`ipc`/`feather`, or `csv`
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "parquet" => Some(Format::Parquet),
+            "arrow" | "ipc" | "feather" => Some(Format::Ipc),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Write `df` to `writer` in `format`, with `provenance` in the file metadata.
+///
+/// CSV is written without provenance; [`write_file`] puts it in a sidecar.
+pub fn write<W: Write>(df: &mut DataFrame, writer: W, format: Format, provenance: &Provenance) -> Result<()> {
+    let entries = provenance.entries();
+    match format {
+        Format::Parquet => ParquetWriter::new(writer)
+            .with_key_value_metadata(Some(KeyValueMetadata::from_static(entries)))
+            .finish(df)
+            .map(drop)
+            .map_err(|e| Error::Custom(format!("Failed to write Parquet: {e}"))),
+        Format::Ipc => {
+            let metadata: BTreeMap<PlSmallStr, PlSmallStr> = entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect();
+            let mut ipc = IpcWriter::new(writer);
+            ipc.set_custom_schema_metadata(Arc::new(metadata));
+            ipc.finish(df)
+                .map_err(|e| Error::Custom(format!("Failed to write Arrow IPC: {e}")))
+        }
+        Format::Csv => CsvWriter::new(writer)
+            .finish(df)
+            .map_err(|e| Error::Custom(format!("Failed to write CSV: {e}"))),
+    }
+}
+
+/// Write `df` to the file at `path`, in the format of its extension.
+///
+/// For CSV, the provenance goes to a JSON object at [`csv_metadata_path`].
+pub fn write_file(df: &mut DataFrame, path: impl AsRef<Path>, provenance: &Provenance) -> Result<()> {
+    let path = path.as_ref();
+    let format = Format::from_path(path)
+        .ok_or_else(|| Error::Custom(format!("Unknown export format for {}", path.display())))?;
+    create(path, |writer| write(df, writer, format, provenance))?;
+    if format == Format::Csv {
+        let entries: serde_json::Map<String, Value> = provenance
+            .entries()
+            .into_iter()
+            .map(|(key, value)| (key, Value::String(value)))
+            .collect();
+        create(&csv_metadata_path(path), |writer| {
+            serde_json::to_writer_pretty(writer, &entries).map_err(Error::from)
+        })?;
+    }
+    Ok(())
+}
+
+/// Where [`write_file`] puts a CSV file's provenance: `ibm.csv` gets
+/// `ibm.csv-metadata.json`, as in the CSV on the Web convention
+pub fn csv_metadata_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut sidecar = path.as_ref().as_os_str().to_owned();
+    sidecar.push("-metadata.json");
+    sidecar.into()
+}
+
+fn create(path: &Path, write: impl FnOnce(&mut BufWriter<File>) -> Result<()>) -> Result<()> {
+    let file = File::create(path).map_err(|e| Error::Custom(format!("Failed to create {}: {e}", path.display())))?;
+    let mut writer = BufWriter::new(file);
+    write(&mut writer)?;
+    writer
+        .flush()
+        .map_err(|e| Error::Custom(format!("Failed to write {}: {e}", path.display())))
+}
+
+/// A tool result's rows as a DataFrame, typed by its schema.
+pub fn tool_result_dataframe(result: &ToolResult) -> Result<DataFrame> {
+    match result {
+        ToolResult::DataFrame(frame) => typed_dataframe(&frame.data, frame.metadata.as_ref(), &frame.schema),
+        _ => Err(Error::Custom("Tool result holds no table to export".to_string())),
+    }
+}
+
+/// Write a tool result to the file at `path`, in the format of its extension.
+///
+/// The symbol, last refreshed time and fetch time come from the result's metadata.
+pub fn write_tool_result(result: &ToolResult, function: &str, path: impl AsRef<Path>) -> Result<()> {
+    let mut df = tool_result_dataframe(result)?;
+    let provenance = match result {
+        ToolResult::DataFrame(frame) => frame.metadata.as_ref().map_or_else(
+            || Provenance::new(function),
+            |metadata| Provenance::from_metadata(function, metadata),
+        ),
+        _ => Provenance::new(function),
+    };
+    write_file(&mut df, path, &provenance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::AlphaVantage;
+    use crate::mock::{Matcher, MockClient, MockResponse};
+    use crate::rest;
+    use crate::tool_use::daily_table;
+    use serde_json::json;
+    use std::io::Cursor;
+
+    fn daily() -> ToolResult {
+        let response = json!({
+            "Meta Data": {"2. Symbol": "IBM", "3. Last Refreshed": "2025-01-08", "5. Time Zone": "US/Eastern"},
+            "Time Series (Daily)": {
+                "2025-01-08": {"1. open": "222.9", "2. high": "223.7", "3. low": "220.8",
+                               "4. close": "223.2", "5. volume": "2796447"}
+            }
+        });
+        let (data, metadata, schema) = daily_table(response).unwrap();
+        ToolResult::columnar(data, schema, metadata)
+    }
+
+    #[test]
+    fn provenance_reads_response_metadata() {
+        let ToolResult::DataFrame(frame) = daily() else {
+            unreachable!()
+        };
+        let provenance = Provenance::from_metadata("TIME_SERIES_DAILY", frame.metadata.as_ref().unwrap());
+        assert_eq!(provenance.symbol.as_deref(), Some("IBM"));
+        assert_eq!(provenance.last_refreshed.as_deref(), Some("2025-01-08"));
+        let keys: Vec<_> = provenance.entries().into_iter().map(|(key, _)| key).collect();
+        assert_eq!(
+            keys,
+            [
+                "alphav.function",
+                "alphav.symbol",
+                "alphav.fetched_at",
+                "alphav.last_refreshed"
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_each_format_with_provenance() {
+        let mut df = tool_result_dataframe(&daily()).unwrap();
+        let provenance = Provenance::new("TIME_SERIES_DAILY").symbol("IBM");
+
+        let mut csv = Vec::new();
+        write(&mut df, &mut csv, Format::Csv, &provenance).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(
+            csv,
+            "date,open,high,low,close,volume\n2025-01-08,222.9,223.7,220.8,223.2,2796447\n"
+        );
+
+        let mut ipc = Vec::new();
+        write(&mut df, &mut ipc, Format::Ipc, &provenance).unwrap();
+        let read = IpcReader::new(Cursor::new(&ipc)).finish().unwrap();
+        assert_eq!(read.height(), 1);
+        let metadata = IpcReader::new(Cursor::new(&ipc)).custom_metadata().unwrap().unwrap();
+        assert_eq!(metadata.get("alphav.symbol").map(|v| v.as_str()), Some("IBM"));
+
+        let mut parquet = Vec::new();
+        write(&mut df, &mut parquet, Format::Parquet, &provenance).unwrap();
+        let mut reader = ParquetReader::new(Cursor::new(&parquet));
+        let metadata = reader.get_metadata().unwrap().key_value_metadata().clone().unwrap();
+        assert!(metadata.iter().any(|kv| kv.key == "alphav.function"));
+        assert_eq!(reader.finish().unwrap().height(), 1);
+    }
+
+    #[test]
+    fn csv_files_read_back_with_sidecar_provenance() {
+        let dir = std::env::temp_dir().join(format!("alphav-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ibm.csv");
+        let ToolResult::DataFrame(frame) = daily() else {
+            unreachable!()
+        };
+        let mut metadata = frame.metadata.clone().unwrap();
+        metadata["fetched_at"] = json!("2025-01-08T21:05:00Z");
+        let result = ToolResult::columnar(frame.data.clone(), frame.schema.clone(), Some(metadata));
+
+        write_tool_result(&result, "TIME_SERIES_DAILY", &path).unwrap();
+
+        let read = CsvReadOptions::default()
+            .try_into_reader_with_file_path(Some(path.clone()))
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(read.shape(), (1, 6));
+        let sidecar = std::fs::read_to_string(csv_metadata_path(&path)).unwrap();
+        let sidecar: Value = serde_json::from_str(&sidecar).unwrap();
+        assert_eq!(sidecar["alphav.symbol"], "IBM");
+        assert_eq!(sidecar["alphav.last_refreshed"], "2025-01-08");
+        assert_eq!(sidecar["alphav.fetched_at"], "2025-01-08T21:05:00Z");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn datasets_keep_response_provenance() {
+        let mock = MockClient::default();
+        mock.on(
+            Matcher::function("TIME_SERIES_DAILY"),
+            MockResponse::ok(
+                r#"{"Meta Data": {"2. Symbol": "IBM", "3. Last Refreshed": "2025-01-08"},
+                    "Time Series (Daily)": {"2025-01-08": {"4. close": "223.2"}}}"#,
+            ),
+        );
+        let client = AlphaVantage::default().with_client(mock.clone()).with_key("test");
+
+        let before = Utc::now();
+        let dataset = rest::time_series::daily(&client, "ibm")
+            .as_dataset()
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(dataset.data.height(), 1);
+        assert_eq!(dataset.provenance.function, "TIME_SERIES_DAILY");
+        assert_eq!(dataset.provenance.symbol.as_deref(), Some("IBM"));
+        assert_eq!(dataset.provenance.last_refreshed.as_deref(), Some("2025-01-08"));
+        assert!(dataset.provenance.fetched_at >= before);
+    }
+
+    #[test]
+    fn format_follows_extension() {
+        assert_eq!(Format::from_path("ibm.parquet"), Some(Format::Parquet));
+        assert_eq!(Format::from_path("ibm.Feather"), Some(Format::Ipc));
+        assert_eq!(Format::from_path("ibm.csv"), Some(Format::Csv));
+        assert_eq!(Format::from_path("ibm.json"), None);
+    }
+}
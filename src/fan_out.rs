@@ -0,0 +1,311 @@
+//! One request per symbol, with bounded concurrency
+//!
+//! Alpha Vantage serves historical series one symbol at a time.
+//! [`AlphaVantage::fan_out`] makes those requests concurrently, at most
+//! [`concurrency`](FanOut::concurrency) at once, and reports a result per symbol
+//! so one failure doesn't abort the batch. Requests refused by a
+//! [`KeyPool`](crate::key_pool::KeyPool) because every key is at its quota wait
+//! for a key to free up.
+//!
+//! ```no_run
+//! use alphav::AlphaVantage;
+//! use alphav::request::common::OutputSize;
+//! use alphav::rest;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let client = AlphaVantage::default().with_key("your_api_key");
+//! let results = client
+//!     .fan_out(["AAPL", "MSFT", "IBM"], |client, symbol| {
+//!         rest::time_series::daily(client, symbol).outputsize(OutputSize::Full)
+//!     })
+//!     .concurrency(2)
+//!     .get()
+//!     .await;
+//!
+//! for (symbol, result) in results {
+//!     match result {
+//!         Ok(json) => println!("{symbol}: {} bytes", json.len()),
+//!         Err(e) => eprintln!("{symbol}: {e}"),
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::stream::{self, Stream, StreamExt};
+
+use crate::client::AlphaVantage;
+use crate::error::{Error, Result};
+use crate::execute::Execute;
+use crate::request::Request;
+
+/// Default number of requests in flight at once
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Default longest time a symbol waits for a rate-limited key pool
+pub const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+impl<Client: Request> AlphaVantage<Client> {
+    /// Make the request built by `request` for each of `symbols`.
+    ///
+    /// Duplicate symbols are requested once. Nothing is sent until
+    /// [`get`](FanOut::get) or [`stream`](FanOut::stream) is called.
+    pub fn fan_out<'a, I, S, F, E>(&'a self, symbols: I, request: F) -> FanOut<'a, Client, F>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+        F: Fn(&'a AlphaVantage<Client>, String) -> E,
+        E: Execute,
+    {
+        let mut seen = HashSet::new();
+        let symbols = symbols
+            .into_iter()
+            .map(Into::into)
+            .filter(|symbol: &String| seen.insert(symbol.clone()))
+            .collect();
+
+        FanOut {
+            client: self,
+            symbols,
+            request,
+            concurrency: DEFAULT_CONCURRENCY,
+            rate_limit_wait: DEFAULT_RATE_LIMIT_WAIT,
+        }
+    }
+}
+
+/// A batch of per-symbol requests, created by [`AlphaVantage::fan_out`]
+pub struct FanOut<'a, Client: Request, F> {
+    client: &'a AlphaVantage<Client>,
+    symbols: Vec<String>,
+    request: F,
+    concurrency: usize,
+    rate_limit_wait: Duration,
+}
+
+impl<'a, Client, F, E> FanOut<'a, Client, F>
+where
+    Client: Request,
+    F: Fn(&'a AlphaVantage<Client>, String) -> E + 'a,
+    E: Execute + 'a,
+{
+    /// Set the most requests in flight at once (at least 1).
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = limit.max(1);
+        self
+    }
+
+    /// Set the longest a symbol waits in total for the key pool to free up before
+    /// failing with [`Error::RateLimited`]. Use [`Duration::ZERO`] to never wait.
+    pub fn rate_limit_wait(mut self, limit: Duration) -> Self {
+        self.rate_limit_wait = limit;
+        self
+    }
+
+    /// Symbols that will be requested, without duplicates.
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+
+    /// Run the batch and return every symbol's result.
+    pub async fn get(self) -> BTreeMap<String, Result<E::Output>> {
+        self.stream().collect().await
+    }
+
+    /// Run the batch, yielding each symbol's result as it completes.
+    pub fn stream(self) -> impl Stream<Item = (String, Result<E::Output>)> + 'a {
+        let FanOut {
+            client,
+            symbols,
+            request,
+            concurrency,
+            rate_limit_wait,
+        } = self;
+        let request = Arc::new(request);
+
+        stream::iter(symbols)
+            .map(move |symbol| {
+                let request = Arc::clone(&request);
+                async move {
+                    let result = fetch(client, &*request, &symbol, rate_limit_wait).await;
+                    (symbol, result)
+                }
+            })
+            .buffer_unordered(concurrency)
+    }
+}
+
+#[cfg(feature = "lazy")]
+impl<'a, Client, F, E> FanOut<'a, Client, F>
+where
+    Client: Request,
+    F: Fn(&'a AlphaVantage<Client>, String) -> E + 'a,
+    E: Execute<Output = polars_lazy::frame::LazyFrame> + 'a,
+{
+    /// Run the batch and stack the symbols' frames into one, tagged with a leading
+    /// `symbol` column, alongside the errors of the symbols that failed.
+    ///
+    /// ```no_run
+    /// use alphav::AlphaVantage;
+    /// use alphav::request::common::OutputSize;
+    /// use alphav::rest;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AlphaVantage::default().with_key("your_api_key");
+    /// let (bars, failed) = client
+    ///     .fan_out(["AAPL", "MSFT", "IBM"], |client, symbol| {
+    ///         rest::time_series::daily(client, symbol).outputsize(OutputSize::Full).as_lazyframe()
+    ///     })
+    ///     .concat()
+    ///     .await?;
+    /// let closes = bars.select([col("symbol"), col("date"), col("close")]).collect()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn concat(self) -> Result<(polars_lazy::frame::LazyFrame, BTreeMap<String, Error>)> {
+        let mut frames = Vec::new();
+        let mut failed = BTreeMap::new();
+        for (symbol, result) in self.get().await {
+            match result {
+                Ok(frame) => frames.push((symbol, frame)),
+                Err(e) => {
+                    failed.insert(symbol, e);
+                }
+            }
+        }
+        Ok((crate::processor::lazy::concat_symbols(frames)?, failed))
+    }
+}
+
+/// Make one symbol's request, waiting out the key pool's rate limit for up to `max_wait`.
+async fn fetch<'a, Client, F, E>(
+    client: &'a AlphaVantage<Client>,
+    request: &F,
+    symbol: &str,
+    max_wait: Duration,
+) -> Result<E::Output>
+where
+    Client: Request,
+    F: Fn(&'a AlphaVantage<Client>, String) -> E,
+    E: Execute,
+{
+    wait_for_rate_limit(max_wait, || request(client, symbol.to_string()).get()).await
+}
+
+/// Run `attempt` until it succeeds or fails with anything but [`Error::RateLimited`],
+/// sleeping as the error asks as long as the total stays within `max_wait`.
+pub(crate) async fn wait_for_rate_limit<T, Fut>(max_wait: Duration, mut attempt: impl FnMut() -> Fut) -> Result<T>
+where
+    Fut: Future<Output = Result<T>>,
+{
+    let mut waited = Duration::ZERO;
+    loop {
+        let retry_after = match attempt().await {
+            Err(Error::RateLimited { retry_after }) if waited + retry_after <= max_wait => retry_after,
+            result => return result,
+        };
+        // Never spin, even if the pool reports a key free right now
+        let retry_after = retry_after.max(Duration::from_millis(10));
+        tokio::time::sleep(retry_after).await;
+        waited += retry_after;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::key_pool::{KeyPool, Quota};
+    use crate::mock::{Matcher, MockClient, MockResponse};
+    use crate::rest;
+
+    fn client(mock: &MockClient) -> AlphaVantage<MockClient> {
+        AlphaVantage::default().with_client(mock.clone()).with_key("test")
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn bounded_concurrency_and_partial_failures() {
+        let delay = Duration::from_millis(10);
+        let mock = MockClient::default();
+        mock.on(
+            Matcher::any().symbol("BAD"),
+            MockResponse::transport_error("unknown symbol").delay(delay),
+        )
+        .on(Matcher::any(), MockResponse::ok("{}").delay(delay));
+        let client = client(&mock);
+        let symbols = ["IBM", "AAPL", "BAD", "MSFT", "IBM", "SPY", "AON"];
+
+        let started = tokio::time::Instant::now();
+        let results = client
+            .fan_out(symbols, rest::time_series::daily)
+            .concurrency(2)
+            .get()
+            .await;
+
+        assert_eq!(results.len(), 6);
+        assert!(results["BAD"].is_err());
+        assert_eq!(results.values().filter(|r| r.is_ok()).count(), 5);
+        // Six distinct symbols, two at a time
+        assert_eq!(started.elapsed(), 3 * delay);
+        assert_eq!(mock.requests().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn respects_key_pool_quota() {
+        let pool = KeyPool::new(["only"]).quota(Quota::per_minute(2));
+        let mock = MockClient::default();
+        mock.on(Matcher::any(), MockResponse::ok("{}"));
+        let client = AlphaVantage::default().with_client(mock).with_key_pool(pool);
+
+        let results: Vec<_> = client
+            .fan_out(["IBM", "AAPL", "MSFT"], rest::fundamentals::company_overview)
+            .rate_limit_wait(Duration::ZERO)
+            .stream()
+            .collect()
+            .await;
+
+        let limited = results
+            .iter()
+            .filter(|(_, result)| matches!(result, Err(Error::RateLimited { .. })))
+            .count();
+        assert_eq!((results.len(), limited), (3, 1));
+    }
+
+    #[cfg(feature = "lazy")]
+    #[tokio::test]
+    async fn concatenates_lazy_frames_by_symbol() {
+        let daily = |close: &str| {
+            let body = serde_json::json!({
+                "Time Series (Daily)": {"2025-01-08": {"4. close": close, "5. volume": "100"}}
+            });
+            MockResponse::ok(body.to_string())
+        };
+        let mock = MockClient::default();
+        mock.on(Matcher::any().symbol("IBM"), daily("223.2"))
+            .on(Matcher::any().symbol("AAPL"), daily("242.7"))
+            .on(Matcher::any(), MockResponse::transport_error("unknown symbol"));
+        let client = client(&mock);
+
+        let (bars, failed) = client
+            .fan_out(["IBM", "AAPL", "BAD"], |client, symbol| {
+                rest::time_series::daily(client, symbol).as_lazyframe()
+            })
+            .concat()
+            .await
+            .unwrap();
+
+        assert_eq!(failed.keys().collect::<Vec<_>>(), ["BAD"]);
+        let df = bars.sort(["symbol"], Default::default()).collect().unwrap();
+        let names: Vec<_> = df.get_column_names().into_iter().map(|name| name.as_str()).collect();
+        assert_eq!(names, ["symbol", "date", "open", "high", "low", "close", "volume"]);
+        let symbols = df.column("symbol").unwrap().str().unwrap();
+        assert_eq!(symbols.into_iter().flatten().collect::<Vec<_>>(), ["AAPL", "IBM"]);
+    }
+}
@@ -0,0 +1,426 @@
+//! Pool of API keys with rotation and per-key quota tracking
+//!
+//! A [`KeyPool`] hands out one key per request according to a [`Strategy`],
+//! counting calls against each key's [`Quota`]. When Alpha Vantage answers
+//! with a rate-limit note, the key is benched (for a minute, or until the
+//! daily quota resets at UTC midnight) and the request is retried with the
+//! next available key.
+//!
+//! ```no_run
+//! use alphav::AlphaVantage;
+//! use alphav::key_pool::{KeyPool, Quota, Strategy};
+//!
+//! let pool = KeyPool::new(["team_a_key", "team_b_key"])
+//!     .strategy(Strategy::LeastUsed)
+//!     .quota(Quota::per_minute(75));
+//! let client = AlphaVantage::default().with_key_pool(pool);
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result};
+use crate::secret::ApiKey;
+
+const MINUTE: Duration = Duration::from_secs(60);
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// How the pool picks the key for the next request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// Cycle through the keys in order
+    #[default]
+    RoundRobin,
+    /// Pick the key with the fewest calls today
+    LeastUsed,
+}
+
+/// Call allowance of a single key. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Quota {
+    /// Calls allowed in any 60 second window
+    pub per_minute: Option<u32>,
+    /// Calls allowed per UTC day
+    pub per_day: Option<u32>,
+}
+
+impl Quota {
+    /// The free tier: 5 calls per minute, 25 per day.
+    pub const FREE: Quota = Quota {
+        per_minute: Some(5),
+        per_day: Some(25),
+    };
+
+    /// A quota limited per minute only, as for premium keys.
+    pub fn per_minute(calls: u32) -> Self {
+        Self {
+            per_minute: Some(calls),
+            per_day: None,
+        }
+    }
+
+    /// Additionally limit calls per UTC day.
+    pub fn per_day(mut self, calls: u32) -> Self {
+        self.per_day = Some(calls);
+        self
+    }
+}
+
+/// Kind of rate limit reported by Alpha Vantage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimit {
+    /// Per-minute call frequency exceeded
+    PerMinute,
+    /// Daily quota exhausted
+    Daily,
+}
+
+/// Snapshot of one key's usage
+#[derive(Debug, Clone)]
+pub struct KeyUsage {
+    /// The key (redacted when formatted)
+    pub key: ApiKey,
+    /// Calls made with this key since the pool was created
+    pub calls: u64,
+    /// Calls left in the current minute, `None` if unlimited
+    pub remaining_per_minute: Option<u32>,
+    /// Calls left today, `None` if unlimited
+    pub remaining_today: Option<u32>,
+    /// Time until the key becomes usable again, if it is currently benched
+    pub limited_for: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct KeyState {
+    key: ApiKey,
+    quota: Option<Quota>,
+    calls: u64,
+    minute: VecDeque<Instant>,
+    day: u64,
+    day_calls: u32,
+    limited_until: Option<Instant>,
+}
+
+impl KeyState {
+    fn new(key: ApiKey, quota: Option<Quota>) -> Self {
+        Self {
+            key,
+            quota,
+            calls: 0,
+            minute: VecDeque::new(),
+            day: utc_day(),
+            day_calls: 0,
+            limited_until: None,
+        }
+    }
+
+    /// Drop calls that left the minute window and reset the daily count at UTC midnight.
+    fn refresh(&mut self, now: Instant, today: u64) {
+        while self.minute.front().is_some_and(|t| now.duration_since(*t) >= MINUTE) {
+            self.minute.pop_front();
+        }
+        if self.day != today {
+            self.day = today;
+            self.day_calls = 0;
+        }
+        if self.limited_until.is_some_and(|until| until <= now) {
+            self.limited_until = None;
+        }
+    }
+
+    fn remaining_per_minute(&self, quota: Quota) -> Option<u32> {
+        quota
+            .per_minute
+            .map(|limit| limit.saturating_sub(self.minute.len() as u32))
+    }
+
+    fn remaining_today(&self, quota: Quota) -> Option<u32> {
+        quota.per_day.map(|limit| limit.saturating_sub(self.day_calls))
+    }
+
+    /// Time until this key may be used again; zero if it is available now.
+    fn wait(&self, now: Instant, quota: Quota) -> Duration {
+        let mut wait = self
+            .limited_until
+            .map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+        if self.remaining_per_minute(quota) == Some(0)
+            && let Some(oldest) = self.minute.front()
+        {
+            wait = wait.max((*oldest + MINUTE).saturating_duration_since(now));
+        }
+        if self.remaining_today(quota) == Some(0) {
+            wait = wait.max(until_utc_midnight());
+        }
+        wait
+    }
+
+    fn record(&mut self, now: Instant) {
+        self.calls += 1;
+        self.day_calls += 1;
+        self.minute.push_back(now);
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    keys: Vec<KeyState>,
+    strategy: Strategy,
+    quota: Quota,
+    cursor: usize,
+}
+
+/// A set of API keys shared by one or more clients
+///
+/// Clones share the same keys and usage counters.
+#[derive(Debug, Clone)]
+pub struct KeyPool {
+    state: Arc<Mutex<State>>,
+}
+
+impl KeyPool {
+    /// Create a pool from `keys` using round-robin selection and no quota.
+    pub fn new<K: Into<ApiKey>>(keys: impl IntoIterator<Item = K>) -> Self {
+        let keys = keys.into_iter().map(|k| KeyState::new(k.into(), None)).collect();
+        Self {
+            state: Arc::new(Mutex::new(State {
+                keys,
+                strategy: Strategy::default(),
+                quota: Quota::default(),
+                cursor: 0,
+            })),
+        }
+    }
+
+    /// Set the selection strategy.
+    pub fn strategy(self, strategy: Strategy) -> Self {
+        self.lock().strategy = strategy;
+        self
+    }
+
+    /// Set the quota for keys that were added without their own.
+    pub fn quota(self, quota: Quota) -> Self {
+        self.lock().quota = quota;
+        self
+    }
+
+    /// Add a key with its own quota.
+    pub fn with_key(self, key: impl Into<ApiKey>, quota: Quota) -> Self {
+        self.lock().keys.push(KeyState::new(key.into(), Some(quota)));
+        self
+    }
+
+    /// Number of keys in the pool.
+    pub fn len(&self) -> usize {
+        self.lock().keys.len()
+    }
+
+    /// Whether the pool has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Current usage of every key, in the order they were added.
+    pub fn usage(&self) -> Vec<KeyUsage> {
+        let now = Instant::now();
+        let today = utc_day();
+        let mut state = self.lock();
+        let default_quota = state.quota;
+        state
+            .keys
+            .iter_mut()
+            .map(|k| {
+                k.refresh(now, today);
+                let quota = k.quota.unwrap_or(default_quota);
+                let limited = k.limited_until.is_some();
+                let wait = k.wait(now, quota);
+                KeyUsage {
+                    key: k.key.clone(),
+                    calls: k.calls,
+                    remaining_per_minute: if limited {
+                        Some(0)
+                    } else {
+                        k.remaining_per_minute(quota)
+                    },
+                    remaining_today: k.remaining_today(quota),
+                    limited_for: (!wait.is_zero()).then_some(wait),
+                }
+            })
+            .collect()
+    }
+
+    /// Pick a key for the next request and count the call against it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MissingApiKey`] if the pool is empty, or
+    /// [`Error::RateLimited`] if every key is out of quota.
+    pub fn acquire(&self) -> Result<ApiKey> {
+        let now = Instant::now();
+        let today = utc_day();
+        let mut state = self.lock();
+        if state.keys.is_empty() {
+            return Err(Error::MissingApiKey);
+        }
+
+        let default_quota = state.quota;
+        let waits: Vec<Duration> = state
+            .keys
+            .iter_mut()
+            .map(|k| {
+                k.refresh(now, today);
+                k.wait(now, k.quota.unwrap_or(default_quota))
+            })
+            .collect();
+
+        let len = state.keys.len();
+        let pick = match state.strategy {
+            Strategy::RoundRobin => (0..len)
+                .map(|offset| (state.cursor + offset) % len)
+                .find(|&i| waits[i].is_zero()),
+            Strategy::LeastUsed => (0..len)
+                .filter(|&i| waits[i].is_zero())
+                .min_by_key(|&i| (state.keys[i].day_calls, state.keys[i].minute.len())),
+        };
+
+        let Some(index) = pick else {
+            let retry_after = waits.into_iter().min().unwrap_or_default();
+            return Err(Error::RateLimited { retry_after });
+        };
+        state.cursor = (index + 1) % len;
+        let key = &mut state.keys[index];
+        key.record(now);
+        Ok(key.key.clone())
+    }
+
+    /// Bench `key` after Alpha Vantage reported `limit` for it.
+    pub fn mark_limited(&self, key: &ApiKey, limit: RateLimit) {
+        let now = Instant::now();
+        let until = match limit {
+            RateLimit::PerMinute => now + MINUTE,
+            RateLimit::Daily => now + until_utc_midnight(),
+        };
+        let mut state = self.lock();
+        if let Some(k) = state.keys.iter_mut().find(|k| k.key == *key) {
+            k.limited_until = Some(k.limited_until.map_or(until, |current| current.max(until)));
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Detect Alpha Vantage's rate-limit responses.
+///
+/// Rate limits come back as `200 OK` with a single `Note` or `Information`
+/// field. Premium-endpoint prompts use `Information` too and are not rate limits.
+pub fn rate_limit(body: &str) -> Option<RateLimit> {
+    // Rate-limit bodies are tiny; don't parse data responses
+    let trimmed = body.trim_start();
+    if !trimmed.starts_with('{') || trimmed.len() > 1024 {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+    let (field, message) = ["Note", "Information"]
+        .into_iter()
+        .find_map(|field| Some((field, value.get(field)?.as_str()?)))?;
+
+    let message = message.to_lowercase();
+    if message.contains("premium endpoint") {
+        None
+    } else if message.contains("per minute") || message.contains("per second") || message.contains("frequency") {
+        Some(RateLimit::PerMinute)
+    } else if message.contains("per day") || message.contains("rate limit") {
+        Some(RateLimit::Daily)
+    } else if field == "Note" {
+        Some(RateLimit::PerMinute)
+    } else {
+        None
+    }
+}
+
+fn utc_day() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / SECS_PER_DAY
+}
+
+fn until_utc_midnight() -> Duration {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    Duration::from_secs(SECS_PER_DAY - secs % SECS_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_robin_skips_exhausted_keys() {
+        let pool = KeyPool::new(["a", "b"]).with_key("c", Quota::per_minute(1));
+
+        let picks: Vec<String> = (0..5).map(|_| pool.acquire().unwrap().expose().to_string()).collect();
+        assert_eq!(picks, ["a", "b", "c", "a", "b"]);
+
+        pool.mark_limited(&ApiKey::new("a"), RateLimit::PerMinute);
+        assert_eq!(pool.acquire().unwrap().expose(), "b");
+
+        let usage = pool.usage();
+        assert_eq!(usage[0].remaining_per_minute, Some(0));
+        assert!(usage[0].limited_for.is_some());
+        assert_eq!(usage[2].remaining_per_minute, Some(0));
+        assert_eq!(usage[1].calls, 3);
+    }
+
+    #[test]
+    fn least_used_and_exhaustion() {
+        let pool = KeyPool::new(["a", "b"])
+            .strategy(Strategy::LeastUsed)
+            .quota(Quota::per_minute(10).per_day(2));
+
+        for _ in 0..4 {
+            pool.acquire().unwrap();
+        }
+        assert!(
+            pool.usage()
+                .iter()
+                .all(|u| u.calls == 2 && u.remaining_today == Some(0))
+        );
+
+        let err = pool.acquire().unwrap_err();
+        assert!(
+            matches!(err, Error::RateLimited { retry_after } if !retry_after.is_zero()),
+            "got: {err:?}"
+        );
+        assert!(matches!(
+            KeyPool::new(Vec::<String>::new()).acquire(),
+            Err(Error::MissingApiKey)
+        ));
+    }
+
+    #[test]
+    fn classifies_rate_limit_messages() {
+        assert_eq!(
+            rate_limit(r#"{"Note": "Our standard API call frequency is 5 calls per minute and 500 calls per day."}"#),
+            Some(RateLimit::PerMinute)
+        );
+        assert_eq!(
+            rate_limit(r#"{"Information": "Our standard API rate limit is 25 requests per day."}"#),
+            Some(RateLimit::Daily)
+        );
+        assert_eq!(
+            rate_limit(
+                r#"{"Information": "Please consider spreading out your free API requests more sparingly (1 request per second)."}"#
+            ),
+            Some(RateLimit::PerMinute)
+        );
+        assert_eq!(rate_limit(r#"{"Information": "This is a premium endpoint."}"#), None);
+        assert_eq!(rate_limit(r#"{"Symbol": "IBM"}"#), None);
+        assert_eq!(rate_limit("timestamp,open\n"), None);
+    }
+}
@@ -41,13 +41,36 @@
 //! # }
 //! ```
 //!
+//! Builders made from an `Arc<AlphaVantage>`, such as [`instance()`], own their
+//! client, so their futures are `Send + 'static` and can be spawned:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use alphav::AlphaVantage;
+//! use alphav::rest::time_series;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Arc::new(AlphaVantage::default().with_key("your_api_key"));
+//! let task = tokio::spawn(time_series::daily(client.clone(), "AAPL").get());
+//! let json = task.await??;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # Features
 //!
 //! - **`hyper`** (default) - Uses [`hyper`](https://docs.rs/hyper) as the HTTP client (lightweight and fast).
 //!
+//! - **`rustls`** - Adds [`TlsBackend::Rustls`](request::TlsBackend) to the `hyper` client, configured through
+//!   [`HyperClientBuilder`](request::HyperClientBuilder).
+//!
 //! - **`reqwest`** - Alternative HTTP client using [`reqwest`](https://docs.rs/reqwest) (more features).
 //!   To use reqwest instead: `default-features = false, features = ["reqwest", "decoder"]`.
 //!
+//! - **`blocking`** - Adds [`blocking::AlphaVantage`], a synchronous facade for code without an async runtime.
+//!   Requires `hyper` or `reqwest`.
+//!
 //! - **`decoder`** (default) - Enables typed response decoding using the [`decoder`](https://docs.rs/decoder) crate.
 //!
 //! - **`dotenvy`** - Enables loading API keys from environment variables via [`dotenvy`](https://docs.rs/dotenvy).
@@ -55,14 +78,38 @@
 //!   Without this feature, use `AlphaVantage::default().with_key("your_key")` instead.
 //!
 //! - **`table`** - Enables Polars DataFrame output via [`polars`](https://docs.rs/polars).
+//!
+//! - **`export`** - Adds [`export`], writing DataFrames and tool results to Parquet, Arrow IPC and CSV files
+//!   with their Alpha Vantage provenance. Implies `table`.
+//!
+//! - **`lazy`** - Adds [`processor::Lazy`] and `as_lazyframe()` for Polars LazyFrame output, and
+//!   [`FanOut::concat`](fan_out::FanOut::concat) to stack per-symbol frames. Implies `table`.
+//!
+//! - **`mock`** - Adds [`mock::MockClient`], a [`Request`] implementation returning canned responses for unit tests.
 
 #![warn(missing_docs)]
 
-mod client;
+pub mod alerts;
+#[cfg(all(feature = "blocking", any(feature = "hyper", feature = "reqwest")))]
+pub mod blocking;
+pub mod cache;
+pub mod cassette;
+pub mod client;
+pub mod credentials;
 pub mod error;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod fan_out;
+pub mod key_pool;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+#[cfg(feature = "decoder")]
+pub mod model;
+pub mod quote_stream;
 pub mod request;
 pub mod response;
 pub mod rest;
+pub mod secret;
 
 pub mod execute;
 pub mod processor;
@@ -71,6 +118,7 @@ pub mod tool_use;
 pub use error::{Error, Result};
 pub use request::Request;
 pub use response::Response;
+pub use secret::ApiKey;
 
 /// The main Alpha Vantage API client with the default HTTP client.
 ///
@@ -0,0 +1,322 @@
+//! Mock HTTP client for unit tests
+//!
+//! [`MockClient`] implements [`Request`] without touching the network. Register
+//! canned responses by `function`/`symbol` matcher, run the code under test, then
+//! inspect the URLs the client received. Responses go through the crate's real
+//! processors, so upstream error shapes (rate-limit notes, premium prompts,
+//! transport failures) can be exercised end to end.
+//!
+//! ```
+//! use alphav::{AlphaVantage, rest};
+//! use alphav::mock::{Matcher, MockClient, MockResponse};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mock = MockClient::default();
+//! mock.on(Matcher::function("OVERVIEW").symbol("IBM"), MockResponse::ok(r#"{"Symbol": "IBM"}"#));
+//! mock.on(Matcher::any(), MockResponse::rate_limited());
+//!
+//! let client = AlphaVantage::default().with_key("test").wrap_client(|_| mock.clone());
+//! let json = rest::fundamentals::company_overview(&client, "IBM").get().await.unwrap();
+//! assert!(json.contains("IBM"));
+//! assert_eq!(mock.requests().len(), 1);
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::cache::query_param;
+use crate::error::{Error, Result};
+use crate::request::{HttpResponse, Request};
+use crate::secret::redact_url;
+
+/// Body Alpha Vantage returns once the daily quota is exhausted
+pub const RATE_LIMIT_INFORMATION: &str = "Thank you for using Alpha Vantage! Our standard API rate limit is 25 requests per day. Please subscribe to any of the premium plans at https://www.alphavantage.co/premium/ to instantly remove all daily rate limits.";
+
+/// Body Alpha Vantage returns when the per-minute call frequency is exceeded
+pub const RATE_LIMIT_NOTE: &str = "Thank you for using Alpha Vantage! Our standard API call frequency is 5 calls per minute and 500 calls per day. Please visit https://www.alphavantage.co/premium/ if you would like to target a higher API call frequency.";
+
+/// Body Alpha Vantage returns for premium-only endpoints on a free key
+pub const PREMIUM_INFORMATION: &str = "Thank you for using Alpha Vantage! This is a premium endpoint. You may subscribe to any of the premium plans at https://www.alphavantage.co/premium/ to instantly unlock all premium endpoints";
+
+/// Selects which requests a canned response applies to
+#[derive(Debug, Clone, Default)]
+pub struct Matcher {
+    function: Option<String>,
+    symbol: Option<String>,
+}
+
+impl Matcher {
+    /// Match every request.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Match requests for an Alpha Vantage `function` (e.g. `TIME_SERIES_DAILY`).
+    pub fn function(function: impl Into<String>) -> Self {
+        Self {
+            function: Some(function.into()),
+            symbol: None,
+        }
+    }
+
+    /// Additionally require `symbol`. For bulk requests, matches if the symbol
+    /// is one of the comma-separated symbols.
+    pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Whether this matcher accepts `url`.
+    pub fn matches(&self, url: &str) -> bool {
+        let function_ok = self
+            .function
+            .as_ref()
+            .is_none_or(|f| query_param(url, "function").as_ref() == Some(f));
+        let symbol_ok = self.symbol.as_ref().is_none_or(|s| {
+            query_param(url, "symbol").is_some_and(|symbols| symbols.split(',').any(|candidate| candidate == s))
+        });
+        function_ok && symbol_ok
+    }
+}
+
+/// A canned response
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    outcome: std::result::Result<(u16, String), String>,
+    delay: Option<Duration>,
+}
+
+impl MockResponse {
+    /// A `200 OK` response with `body`.
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self::status(200, body)
+    }
+
+    /// A response with an arbitrary status code.
+    pub fn status(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            outcome: Ok((status, body.into())),
+            delay: None,
+        }
+    }
+
+    /// The daily rate-limit `Information` message.
+    pub fn rate_limited() -> Self {
+        Self::ok(serde_json::json!({ "Information": RATE_LIMIT_INFORMATION }).to_string())
+    }
+
+    /// The per-minute rate-limit `Note` message.
+    pub fn rate_limit_note() -> Self {
+        Self::ok(serde_json::json!({ "Note": RATE_LIMIT_NOTE }).to_string())
+    }
+
+    /// The premium-endpoint `Information` message.
+    pub fn premium_only() -> Self {
+        Self::ok(serde_json::json!({ "Information": PREMIUM_INFORMATION }).to_string())
+    }
+
+    /// The `Error Message` returned for invalid symbols or parameters.
+    pub fn invalid_call(function: &str) -> Self {
+        let message = format!(
+            "Invalid API call. Please retry or visit the documentation (https://www.alphavantage.co/documentation/) for {function}."
+        );
+        Self::ok(serde_json::json!({ "Error Message": message }).to_string())
+    }
+
+    /// A transport failure: the request errors before any response is received.
+    pub fn transport_error(message: impl Into<String>) -> Self {
+        Self {
+            outcome: Err(message.into()),
+            delay: None,
+        }
+    }
+
+    /// Wait `delay` before responding.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Route {
+    matcher: Matcher,
+    responses: VecDeque<MockResponse>,
+    last: MockResponse,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    routes: Vec<Route>,
+    requests: Vec<String>,
+}
+
+/// HTTP client returning canned responses
+///
+/// Clones share the same routes and request log, so a clone can be handed to
+/// [`AlphaVantage`](crate::client::AlphaVantage) while the test keeps the original.
+#[derive(Debug, Clone, Default)]
+pub struct MockClient {
+    state: Arc<Mutex<State>>,
+}
+
+impl MockClient {
+    /// Register a response for requests accepted by `matcher`.
+    ///
+    /// Routes are tried in registration order. Registering several responses for
+    /// the same matcher plays them in sequence; the last one repeats once the
+    /// sequence is exhausted.
+    pub fn on(&self, matcher: Matcher, response: MockResponse) -> &Self {
+        self.on_sequence(matcher, [response])
+    }
+
+    /// Register a sequence of responses for requests accepted by `matcher`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `responses` is empty.
+    pub fn on_sequence(&self, matcher: Matcher, responses: impl IntoIterator<Item = MockResponse>) -> &Self {
+        let mut responses: VecDeque<MockResponse> = responses.into_iter().collect();
+        let mut state = self.lock();
+        if let Some(route) = state
+            .routes
+            .iter_mut()
+            .find(|r| r.matcher.function == matcher.function && r.matcher.symbol == matcher.symbol)
+        {
+            if let Some(last) = responses.back() {
+                route.last = last.clone();
+            }
+            route.responses.append(&mut responses);
+        } else {
+            let last = responses.back().cloned().expect("at least one mock response");
+            state.routes.push(Route {
+                matcher,
+                responses,
+                last,
+            });
+        }
+        self
+    }
+
+    /// URLs of every request received, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.lock().requests.clone()
+    }
+
+    /// Number of received requests accepted by `matcher`.
+    pub fn calls(&self, matcher: &Matcher) -> usize {
+        self.lock().requests.iter().filter(|url| matcher.matches(url)).count()
+    }
+
+    /// Remove all routes and forget received requests.
+    pub fn reset(&self) {
+        *self.lock() = State::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn next_response(&self, url: &str) -> Result<MockResponse> {
+        let mut state = self.lock();
+        state.requests.push(url.to_string());
+        let route = state
+            .routes
+            .iter_mut()
+            .find(|r| r.matcher.matches(url))
+            .ok_or_else(|| Error::Custom(format!("MockClient: no response registered for {}", redact_url(url))))?;
+        Ok(route.responses.pop_front().unwrap_or_else(|| route.last.clone()))
+    }
+
+    async fn respond(&self, url: &str) -> Result<HttpResponse> {
+        let response = self.next_response(url)?;
+        if let Some(delay) = response.delay {
+            tokio::time::sleep(delay).await;
+        }
+        match response.outcome {
+            Ok((status, body)) => Ok(HttpResponse::new(status, body, None)),
+            Err(message) => Err(Error::Custom(format!("HTTP request failed: {message}"))),
+        }
+    }
+}
+
+impl Request for MockClient {
+    type Response = HttpResponse;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    async fn get(&self, url: &str) -> Result<Self::Response> {
+        self.respond(url).await
+    }
+
+    async fn post(&self, url: &str, _body: &str) -> Result<Self::Response> {
+        self.respond(url).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::AlphaVantage;
+    use crate::rest;
+
+    fn client(mock: &MockClient) -> AlphaVantage<MockClient> {
+        AlphaVantage::default().with_client(mock.clone()).with_key("test")
+    }
+
+    #[tokio::test]
+    async fn routes_by_function_and_symbol() {
+        let mock = MockClient::default();
+        mock.on(
+            Matcher::function("TIME_SERIES_DAILY").symbol("IBM"),
+            MockResponse::ok("ibm"),
+        )
+        .on(Matcher::function("TIME_SERIES_DAILY"), MockResponse::ok("other"));
+        let client = client(&mock);
+
+        assert_eq!(rest::time_series::daily(&client, "IBM").get().await.unwrap(), "ibm");
+        assert_eq!(rest::time_series::daily(&client, "AAPL").get().await.unwrap(), "other");
+        assert!(rest::time_series::weekly(&client, "IBM").get().await.is_err());
+
+        assert_eq!(mock.requests().len(), 3);
+        assert_eq!(mock.calls(&Matcher::function("TIME_SERIES_DAILY")), 2);
+        assert!(mock.requests()[0].contains("symbol=IBM"));
+    }
+
+    #[tokio::test]
+    async fn plays_sequences_and_repeats_last() {
+        let mock = MockClient::default();
+        mock.on_sequence(
+            Matcher::any(),
+            [MockResponse::ok("first"), MockResponse::status(503, "unavailable")],
+        );
+        let client = client(&mock);
+
+        assert_eq!(rest::time_series::daily(&client, "IBM").get().await.unwrap(), "first");
+        for _ in 0..2 {
+            let err = rest::time_series::daily(&client, "IBM").get().await.unwrap_err();
+            assert!(matches!(err, Error::ApiError { status: 503, .. }), "got: {err:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn simulates_transport_errors_and_bulk_symbols() {
+        let mock = MockClient::default();
+        mock.on(
+            Matcher::function("REALTIME_BULK_QUOTES").symbol("SPY"),
+            MockResponse::transport_error("connection reset").delay(Duration::from_millis(5)),
+        );
+        let client = client(&mock);
+
+        let err = rest::quotes::realtime_bulk(&client, ["AON", "SPY"])
+            .get()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("connection reset"), "got: {err}");
+    }
+}
@@ -0,0 +1,11 @@
+//! Typed Alpha Vantage responses, produced by the [`rest::decoded`](crate::rest::decoded) endpoints
+mod de;
+pub mod fundamentals;
+pub mod time_series;
+
+pub use fundamentals::{
+    AnnualEarnings, BalanceSheetReport, BalanceSheets, CashFlowReport, CashFlows, CompanyOverview, Earnings,
+    EarningsEstimate, EarningsEstimates, IncomeStatementReport, IncomeStatements, QuarterlyEarnings, ReportTime,
+    Statements,
+};
+pub use time_series::{Bar, MetaData, TimeSeries};
@@ -0,0 +1,75 @@
+//! Deserializers for Alpha Vantage's string-encoded values
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde::de::{Deserializer, Error};
+use serde_json::Value;
+
+use crate::processor::json::path_error;
+use crate::tool_use::missing_data;
+
+/// Parse a JSON response into `T`, surfacing Alpha Vantage's own message when `key` is absent.
+pub(crate) fn from_json<T: serde::de::DeserializeOwned>(body: &str, key: &str) -> crate::error::Result<T> {
+    let response: Value = serde_json::from_str(body)?;
+    if response.get(key).is_none() {
+        return Err(missing_data(&response, key));
+    }
+    serde_path_to_error::deserialize(&response).map_err(path_error)
+}
+
+/// A string or number, with `"None"`, `"-"` and empty strings as missing.
+fn value<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) if !matches!(s.trim(), "" | "None" | "-") => Some(s),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+/// Optional string, with `"None"` as `None`
+pub(crate) fn text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    value(deserializer)
+}
+
+/// Decimal number such as `"32.6"`, with `"None"` as `None`
+pub(crate) fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    value(deserializer)?
+        .map(|s| s.parse().map_err(|_| D::Error::custom(format!("invalid number: {s}"))))
+        .transpose()
+}
+
+/// Percentage such as `"4.12"` or `"4.12%"`, as a fraction (`0.0412`)
+pub(crate) fn percent<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    value(deserializer)?
+        .map(|s| {
+            s.trim_end_matches('%')
+                .parse::<f64>()
+                .map(|p| p / 100.0)
+                .map_err(|_| D::Error::custom(format!("invalid percentage: {s}")))
+        })
+        .transpose()
+}
+
+/// Whole amount such as `"1234000000"`, with `"None"` as `None`
+pub(crate) fn amount<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    value(deserializer)?
+        .map(|s| {
+            s.parse::<i64>()
+                .or_else(|_| s.parse::<f64>().map(|f| f.round() as i64))
+                .map_err(|_| D::Error::custom(format!("invalid amount: {s}")))
+        })
+        .transpose()
+}
+
+/// `YYYY-MM-DD` date
+pub(crate) fn date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|_| D::Error::custom(format!("invalid date: {s}")))
+}
+
+/// Optional `YYYY-MM-DD` date, with `"None"` as `None`
+pub(crate) fn optional_date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NaiveDate>, D::Error> {
+    value(deserializer)?
+        .map(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|_| D::Error::custom(format!("invalid date: {s}"))))
+        .transpose()
+}
@@ -0,0 +1,698 @@
+//! Typed fundamental data responses
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::error::Result;
+use crate::model::de;
+
+/// Annual and quarterly reports of one financial statement
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Statements<R> {
+    /// Stock symbol
+    pub symbol: String,
+    /// Reports per fiscal year, most recent first; empty if the response has none
+    #[serde(default = "Vec::new")]
+    pub annual_reports: Vec<R>,
+    /// Reports per fiscal quarter, most recent first; empty if the response has none
+    #[serde(default = "Vec::new")]
+    pub quarterly_reports: Vec<R>,
+}
+
+impl<R: serde::de::DeserializeOwned> Statements<R> {
+    /// Parse an `INCOME_STATEMENT`, `BALANCE_SHEET` or `CASH_FLOW` JSON response.
+    pub fn from_json(body: &str) -> Result<Self> {
+        de::from_json(body, "symbol")
+    }
+}
+
+/// Defines a statement report: its fiscal period plus `Option<i64>` line items.
+macro_rules! report {
+    ($(#[$meta:meta])* $name:ident { $($(#[$doc:meta])* $field:ident: $key:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Deserialize)]
+        pub struct $name {
+            /// Last day of the fiscal period
+            #[serde(rename = "fiscalDateEnding", deserialize_with = "de::date")]
+            pub fiscal_date_ending: NaiveDate,
+            /// Currency of the amounts, e.g. `USD`
+            #[serde(rename = "reportedCurrency")]
+            pub reported_currency: String,
+            $(
+                $(#[$doc])*
+                #[serde(rename = $key, default, deserialize_with = "de::amount")]
+                pub $field: Option<i64>,
+            )*
+        }
+    };
+}
+
+report! {
+    /// Income statement for one fiscal period
+    IncomeStatementReport {
+        /// Gross profit
+        gross_profit: "grossProfit",
+        /// Total revenue
+        total_revenue: "totalRevenue",
+        /// Cost of revenue
+        cost_of_revenue: "costOfRevenue",
+        /// Cost of goods and services sold
+        cost_of_goods_and_services_sold: "costofGoodsAndServicesSold",
+        /// Operating income
+        operating_income: "operatingIncome",
+        /// Selling, general and administrative expenses
+        selling_general_and_administrative: "sellingGeneralAndAdministrative",
+        /// Research and development expenses
+        research_and_development: "researchAndDevelopment",
+        /// Operating expenses
+        operating_expenses: "operatingExpenses",
+        /// Net investment income
+        investment_income_net: "investmentIncomeNet",
+        /// Net interest income
+        net_interest_income: "netInterestIncome",
+        /// Interest income
+        interest_income: "interestIncome",
+        /// Interest expense
+        interest_expense: "interestExpense",
+        /// Non-interest income
+        non_interest_income: "nonInterestIncome",
+        /// Other non-operating income
+        other_non_operating_income: "otherNonOperatingIncome",
+        /// Depreciation
+        depreciation: "depreciation",
+        /// Depreciation and amortization
+        depreciation_and_amortization: "depreciationAndAmortization",
+        /// Income before tax
+        income_before_tax: "incomeBeforeTax",
+        /// Income tax expense
+        income_tax_expense: "incomeTaxExpense",
+        /// Interest and debt expense
+        interest_and_debt_expense: "interestAndDebtExpense",
+        /// Net income from continuing operations
+        net_income_from_continuing_operations: "netIncomeFromContinuingOperations",
+        /// Comprehensive income, net of tax
+        comprehensive_income_net_of_tax: "comprehensiveIncomeNetOfTax",
+        /// Earnings before interest and taxes
+        ebit: "ebit",
+        /// Earnings before interest, taxes, depreciation and amortization
+        ebitda: "ebitda",
+        /// Net income
+        net_income: "netIncome",
+    }
+}
+
+report! {
+    /// Balance sheet at the end of one fiscal period
+    BalanceSheetReport {
+        /// Total assets
+        total_assets: "totalAssets",
+        /// Total current assets
+        total_current_assets: "totalCurrentAssets",
+        /// Cash and cash equivalents at carrying value
+        cash_and_cash_equivalents_at_carrying_value: "cashAndCashEquivalentsAtCarryingValue",
+        /// Cash and short-term investments
+        cash_and_short_term_investments: "cashAndShortTermInvestments",
+        /// Inventory
+        inventory: "inventory",
+        /// Current net receivables
+        current_net_receivables: "currentNetReceivables",
+        /// Total non-current assets
+        total_non_current_assets: "totalNonCurrentAssets",
+        /// Property, plant and equipment
+        property_plant_equipment: "propertyPlantEquipment",
+        /// Accumulated depreciation and amortization of property, plant and equipment
+        accumulated_depreciation_amortization_ppe: "accumulatedDepreciationAmortizationPPE",
+        /// Intangible assets
+        intangible_assets: "intangibleAssets",
+        /// Intangible assets excluding goodwill
+        intangible_assets_excluding_goodwill: "intangibleAssetsExcludingGoodwill",
+        /// Goodwill
+        goodwill: "goodwill",
+        /// Investments
+        investments: "investments",
+        /// Long-term investments
+        long_term_investments: "longTermInvestments",
+        /// Short-term investments
+        short_term_investments: "shortTermInvestments",
+        /// Other current assets
+        other_current_assets: "otherCurrentAssets",
+        /// Other non-current assets
+        other_non_current_assets: "otherNonCurrentAssets",
+        /// Total liabilities
+        total_liabilities: "totalLiabilities",
+        /// Total current liabilities
+        total_current_liabilities: "totalCurrentLiabilities",
+        /// Current accounts payable
+        current_accounts_payable: "currentAccountsPayable",
+        /// Deferred revenue
+        deferred_revenue: "deferredRevenue",
+        /// Current debt
+        current_debt: "currentDebt",
+        /// Short-term debt
+        short_term_debt: "shortTermDebt",
+        /// Total non-current liabilities
+        total_non_current_liabilities: "totalNonCurrentLiabilities",
+        /// Capital lease obligations
+        capital_lease_obligations: "capitalLeaseObligations",
+        /// Long-term debt
+        long_term_debt: "longTermDebt",
+        /// Current portion of long-term debt
+        current_long_term_debt: "currentLongTermDebt",
+        /// Non-current portion of long-term debt
+        long_term_debt_noncurrent: "longTermDebtNoncurrent",
+        /// Short- and long-term debt
+        short_long_term_debt_total: "shortLongTermDebtTotal",
+        /// Other current liabilities
+        other_current_liabilities: "otherCurrentLiabilities",
+        /// Other non-current liabilities
+        other_non_current_liabilities: "otherNonCurrentLiabilities",
+        /// Total shareholder equity
+        total_shareholder_equity: "totalShareholderEquity",
+        /// Treasury stock
+        treasury_stock: "treasuryStock",
+        /// Retained earnings
+        retained_earnings: "retainedEarnings",
+        /// Common stock
+        common_stock: "commonStock",
+        /// Common shares outstanding
+        common_stock_shares_outstanding: "commonStockSharesOutstanding",
+    }
+}
+
+report! {
+    /// Cash flow statement for one fiscal period
+    CashFlowReport {
+        /// Operating cash flow
+        operating_cashflow: "operatingCashflow",
+        /// Payments for operating activities
+        payments_for_operating_activities: "paymentsForOperatingActivities",
+        /// Proceeds from operating activities
+        proceeds_from_operating_activities: "proceedsFromOperatingActivities",
+        /// Change in operating liabilities
+        change_in_operating_liabilities: "changeInOperatingLiabilities",
+        /// Change in operating assets
+        change_in_operating_assets: "changeInOperatingAssets",
+        /// Depreciation, depletion and amortization
+        depreciation_depletion_and_amortization: "depreciationDepletionAndAmortization",
+        /// Capital expenditures
+        capital_expenditures: "capitalExpenditures",
+        /// Change in receivables
+        change_in_receivables: "changeInReceivables",
+        /// Change in inventory
+        change_in_inventory: "changeInInventory",
+        /// Profit or loss
+        profit_loss: "profitLoss",
+        /// Cash flow from investment
+        cashflow_from_investment: "cashflowFromInvestment",
+        /// Cash flow from financing
+        cashflow_from_financing: "cashflowFromFinancing",
+        /// Proceeds from repayments of short-term debt
+        proceeds_from_repayments_of_short_term_debt: "proceedsFromRepaymentsOfShortTermDebt",
+        /// Payments for repurchase of common stock
+        payments_for_repurchase_of_common_stock: "paymentsForRepurchaseOfCommonStock",
+        /// Payments for repurchase of equity
+        payments_for_repurchase_of_equity: "paymentsForRepurchaseOfEquity",
+        /// Payments for repurchase of preferred stock
+        payments_for_repurchase_of_preferred_stock: "paymentsForRepurchaseOfPreferredStock",
+        /// Dividend payout
+        dividend_payout: "dividendPayout",
+        /// Dividend payout on common stock
+        dividend_payout_common_stock: "dividendPayoutCommonStock",
+        /// Dividend payout on preferred stock
+        dividend_payout_preferred_stock: "dividendPayoutPreferredStock",
+        /// Proceeds from issuance of common stock
+        proceeds_from_issuance_of_common_stock: "proceedsFromIssuanceOfCommonStock",
+        /// Net proceeds from issuance of long-term debt and capital securities
+        proceeds_from_issuance_of_long_term_debt_and_capital_securities_net:
+            "proceedsFromIssuanceOfLongTermDebtAndCapitalSecuritiesNet",
+        /// Proceeds from issuance of preferred stock
+        proceeds_from_issuance_of_preferred_stock: "proceedsFromIssuanceOfPreferredStock",
+        /// Proceeds from repurchase of equity
+        proceeds_from_repurchase_of_equity: "proceedsFromRepurchaseOfEquity",
+        /// Proceeds from sale of treasury stock
+        proceeds_from_sale_of_treasury_stock: "proceedsFromSaleOfTreasuryStock",
+        /// Change in cash and cash equivalents
+        change_in_cash_and_cash_equivalents: "changeInCashAndCashEquivalents",
+        /// Change in exchange rate
+        change_in_exchange_rate: "changeInExchangeRate",
+        /// Net income
+        net_income: "netIncome",
+    }
+}
+
+/// `OVERVIEW` response: company profile, valuation and analyst ratings
+///
+/// Margins, returns, yields and growth rates are fractions (`0.15` for 15%).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CompanyOverview {
+    /// Stock symbol
+    pub symbol: String,
+    /// Asset type, e.g. `Common Stock`
+    pub asset_type: String,
+    /// Company name
+    pub name: String,
+    /// Business description
+    #[serde(default, deserialize_with = "de::text")]
+    pub description: Option<String>,
+    /// SEC Central Index Key
+    #[serde(rename = "CIK", default, deserialize_with = "de::text")]
+    pub cik: Option<String>,
+    /// Listing exchange
+    #[serde(default, deserialize_with = "de::text")]
+    pub exchange: Option<String>,
+    /// Trading currency
+    #[serde(default, deserialize_with = "de::text")]
+    pub currency: Option<String>,
+    /// Country of incorporation
+    #[serde(default, deserialize_with = "de::text")]
+    pub country: Option<String>,
+    /// Sector
+    #[serde(default, deserialize_with = "de::text")]
+    pub sector: Option<String>,
+    /// Industry
+    #[serde(default, deserialize_with = "de::text")]
+    pub industry: Option<String>,
+    /// Headquarters address
+    #[serde(default, deserialize_with = "de::text")]
+    pub address: Option<String>,
+    /// Company website
+    #[serde(default, deserialize_with = "de::text")]
+    pub official_site: Option<String>,
+    /// Month the fiscal year ends, e.g. `December`
+    #[serde(default, deserialize_with = "de::text")]
+    pub fiscal_year_end: Option<String>,
+    /// End of the latest reported quarter
+    #[serde(default, deserialize_with = "de::optional_date")]
+    pub latest_quarter: Option<NaiveDate>,
+    /// Market capitalization
+    #[serde(default, deserialize_with = "de::amount")]
+    pub market_capitalization: Option<i64>,
+    /// Trailing EBITDA
+    #[serde(rename = "EBITDA", default, deserialize_with = "de::amount")]
+    pub ebitda: Option<i64>,
+    /// Price to earnings ratio
+    #[serde(rename = "PERatio", default, deserialize_with = "de::number")]
+    pub pe_ratio: Option<f64>,
+    /// Price/earnings to growth ratio
+    #[serde(rename = "PEGRatio", default, deserialize_with = "de::number")]
+    pub peg_ratio: Option<f64>,
+    /// Book value per share
+    #[serde(default, deserialize_with = "de::number")]
+    pub book_value: Option<f64>,
+    /// Annual dividend per share
+    #[serde(default, deserialize_with = "de::number")]
+    pub dividend_per_share: Option<f64>,
+    /// Dividend yield, as a fraction
+    #[serde(default, deserialize_with = "de::number")]
+    pub dividend_yield: Option<f64>,
+    /// Trailing earnings per share
+    #[serde(rename = "EPS", default, deserialize_with = "de::number")]
+    pub eps: Option<f64>,
+    /// Trailing revenue per share
+    #[serde(rename = "RevenuePerShareTTM", default, deserialize_with = "de::number")]
+    pub revenue_per_share_ttm: Option<f64>,
+    /// Profit margin, as a fraction
+    #[serde(default, deserialize_with = "de::number")]
+    pub profit_margin: Option<f64>,
+    /// Trailing operating margin, as a fraction
+    #[serde(rename = "OperatingMarginTTM", default, deserialize_with = "de::number")]
+    pub operating_margin_ttm: Option<f64>,
+    /// Trailing return on assets, as a fraction
+    #[serde(rename = "ReturnOnAssetsTTM", default, deserialize_with = "de::number")]
+    pub return_on_assets_ttm: Option<f64>,
+    /// Trailing return on equity, as a fraction
+    #[serde(rename = "ReturnOnEquityTTM", default, deserialize_with = "de::number")]
+    pub return_on_equity_ttm: Option<f64>,
+    /// Trailing revenue
+    #[serde(rename = "RevenueTTM", default, deserialize_with = "de::amount")]
+    pub revenue_ttm: Option<i64>,
+    /// Trailing gross profit
+    #[serde(rename = "GrossProfitTTM", default, deserialize_with = "de::amount")]
+    pub gross_profit_ttm: Option<i64>,
+    /// Trailing diluted earnings per share
+    #[serde(rename = "DilutedEPSTTM", default, deserialize_with = "de::number")]
+    pub diluted_eps_ttm: Option<f64>,
+    /// Year-over-year quarterly earnings growth, as a fraction
+    #[serde(rename = "QuarterlyEarningsGrowthYOY", default, deserialize_with = "de::number")]
+    pub quarterly_earnings_growth_yoy: Option<f64>,
+    /// Year-over-year quarterly revenue growth, as a fraction
+    #[serde(rename = "QuarterlyRevenueGrowthYOY", default, deserialize_with = "de::number")]
+    pub quarterly_revenue_growth_yoy: Option<f64>,
+    /// Consensus analyst price target
+    #[serde(default, deserialize_with = "de::number")]
+    pub analyst_target_price: Option<f64>,
+    /// Number of strong buy ratings
+    #[serde(default, deserialize_with = "de::amount")]
+    pub analyst_rating_strong_buy: Option<i64>,
+    /// Number of buy ratings
+    #[serde(default, deserialize_with = "de::amount")]
+    pub analyst_rating_buy: Option<i64>,
+    /// Number of hold ratings
+    #[serde(default, deserialize_with = "de::amount")]
+    pub analyst_rating_hold: Option<i64>,
+    /// Number of sell ratings
+    #[serde(default, deserialize_with = "de::amount")]
+    pub analyst_rating_sell: Option<i64>,
+    /// Number of strong sell ratings
+    #[serde(default, deserialize_with = "de::amount")]
+    pub analyst_rating_strong_sell: Option<i64>,
+    /// Trailing price to earnings ratio
+    #[serde(rename = "TrailingPE", default, deserialize_with = "de::number")]
+    pub trailing_pe: Option<f64>,
+    /// Forward price to earnings ratio
+    #[serde(rename = "ForwardPE", default, deserialize_with = "de::number")]
+    pub forward_pe: Option<f64>,
+    /// Trailing price to sales ratio
+    #[serde(rename = "PriceToSalesRatioTTM", default, deserialize_with = "de::number")]
+    pub price_to_sales_ratio_ttm: Option<f64>,
+    /// Price to book ratio
+    #[serde(default, deserialize_with = "de::number")]
+    pub price_to_book_ratio: Option<f64>,
+    /// Enterprise value to revenue
+    #[serde(rename = "EVToRevenue", default, deserialize_with = "de::number")]
+    pub ev_to_revenue: Option<f64>,
+    /// Enterprise value to EBITDA
+    #[serde(rename = "EVToEBITDA", default, deserialize_with = "de::number")]
+    pub ev_to_ebitda: Option<f64>,
+    /// Beta against the market
+    #[serde(default, deserialize_with = "de::number")]
+    pub beta: Option<f64>,
+    /// 52-week high price
+    #[serde(rename = "52WeekHigh", default, deserialize_with = "de::number")]
+    pub week_52_high: Option<f64>,
+    /// 52-week low price
+    #[serde(rename = "52WeekLow", default, deserialize_with = "de::number")]
+    pub week_52_low: Option<f64>,
+    /// 50-day moving average price
+    #[serde(rename = "50DayMovingAverage", default, deserialize_with = "de::number")]
+    pub day_50_moving_average: Option<f64>,
+    /// 200-day moving average price
+    #[serde(rename = "200DayMovingAverage", default, deserialize_with = "de::number")]
+    pub day_200_moving_average: Option<f64>,
+    /// Shares outstanding
+    #[serde(default, deserialize_with = "de::amount")]
+    pub shares_outstanding: Option<i64>,
+    /// Shares available for trading
+    #[serde(default, deserialize_with = "de::amount")]
+    pub shares_float: Option<i64>,
+    /// Share of stock held by insiders, as a fraction
+    #[serde(default, deserialize_with = "de::percent")]
+    pub percent_insiders: Option<f64>,
+    /// Share of stock held by institutions, as a fraction
+    #[serde(default, deserialize_with = "de::percent")]
+    pub percent_institutions: Option<f64>,
+    /// Next or latest dividend payment date
+    #[serde(default, deserialize_with = "de::optional_date")]
+    pub dividend_date: Option<NaiveDate>,
+    /// Next or latest ex-dividend date
+    #[serde(default, deserialize_with = "de::optional_date")]
+    pub ex_dividend_date: Option<NaiveDate>,
+}
+
+impl CompanyOverview {
+    /// Parse an `OVERVIEW` JSON response.
+    pub fn from_json(body: &str) -> Result<Self> {
+        de::from_json(body, "Symbol")
+    }
+}
+
+/// `EARNINGS` response: reported and estimated EPS per period
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Earnings {
+    /// Stock symbol
+    pub symbol: String,
+    /// Earnings per fiscal year, most recent first
+    pub annual_earnings: Vec<AnnualEarnings>,
+    /// Earnings per fiscal quarter, most recent first
+    pub quarterly_earnings: Vec<QuarterlyEarnings>,
+}
+
+impl Earnings {
+    /// Parse an `EARNINGS` JSON response.
+    pub fn from_json(body: &str) -> Result<Self> {
+        de::from_json(body, "annualEarnings")
+    }
+}
+
+/// Earnings for one fiscal year
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnualEarnings {
+    /// Last day of the fiscal year
+    #[serde(deserialize_with = "de::date")]
+    pub fiscal_date_ending: NaiveDate,
+    /// Reported earnings per share
+    #[serde(rename = "reportedEPS", default, deserialize_with = "de::number")]
+    pub reported_eps: Option<f64>,
+}
+
+/// Earnings for one fiscal quarter
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarterlyEarnings {
+    /// Last day of the fiscal quarter
+    #[serde(deserialize_with = "de::date")]
+    pub fiscal_date_ending: NaiveDate,
+    /// Day the results were announced
+    #[serde(default, deserialize_with = "de::optional_date")]
+    pub reported_date: Option<NaiveDate>,
+    /// Reported earnings per share
+    #[serde(rename = "reportedEPS", default, deserialize_with = "de::number")]
+    pub reported_eps: Option<f64>,
+    /// Consensus estimate of earnings per share
+    #[serde(rename = "estimatedEPS", default, deserialize_with = "de::number")]
+    pub estimated_eps: Option<f64>,
+    /// Reported minus estimated earnings per share
+    #[serde(default, deserialize_with = "de::number")]
+    pub surprise: Option<f64>,
+    /// Surprise relative to the estimate, as a fraction
+    #[serde(default, deserialize_with = "de::percent")]
+    pub surprise_percentage: Option<f64>,
+    /// When in the trading day the results were announced
+    #[serde(default)]
+    pub report_time: Option<ReportTime>,
+}
+
+/// When in the trading day earnings were announced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReportTime {
+    /// Before the market opens
+    PreMarket,
+    /// After the market closes
+    PostMarket,
+    /// Any other value
+    #[serde(other)]
+    Unknown,
+}
+
+/// `EARNINGS_ESTIMATES` response: analyst EPS and revenue estimates per horizon
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EarningsEstimates {
+    /// Stock symbol
+    pub symbol: String,
+    /// Estimates, one per fiscal period and horizon
+    pub estimates: Vec<EarningsEstimate>,
+}
+
+impl EarningsEstimates {
+    /// Parse an `EARNINGS_ESTIMATES` JSON response.
+    pub fn from_json(body: &str) -> Result<Self> {
+        de::from_json(body, "estimates")
+    }
+
+    /// First estimate whose horizon contains `horizon`, e.g. `"next fiscal year"`
+    pub fn horizon(&self, horizon: &str) -> Option<&EarningsEstimate> {
+        self.estimates
+            .iter()
+            .find(|estimate| estimate.horizon.contains(horizon))
+    }
+}
+
+/// Analyst estimates for one fiscal period
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EarningsEstimate {
+    /// Last day of the fiscal period
+    #[serde(deserialize_with = "de::date")]
+    pub date: NaiveDate,
+    /// Period relative to today, e.g. `next fiscal year` or `historical fiscal quarter`
+    pub horizon: String,
+    /// Average EPS estimate
+    #[serde(default, deserialize_with = "de::number")]
+    pub eps_estimate_average: Option<f64>,
+    /// Highest EPS estimate
+    #[serde(default, deserialize_with = "de::number")]
+    pub eps_estimate_high: Option<f64>,
+    /// Lowest EPS estimate
+    #[serde(default, deserialize_with = "de::number")]
+    pub eps_estimate_low: Option<f64>,
+    /// Number of analysts estimating EPS
+    #[serde(default, deserialize_with = "de::amount")]
+    pub eps_estimate_analyst_count: Option<i64>,
+    /// Average EPS estimate 7 days ago
+    #[serde(default, deserialize_with = "de::number")]
+    pub eps_estimate_average_7_days_ago: Option<f64>,
+    /// Average EPS estimate 30 days ago
+    #[serde(default, deserialize_with = "de::number")]
+    pub eps_estimate_average_30_days_ago: Option<f64>,
+    /// Average EPS estimate 60 days ago
+    #[serde(default, deserialize_with = "de::number")]
+    pub eps_estimate_average_60_days_ago: Option<f64>,
+    /// Average EPS estimate 90 days ago
+    #[serde(default, deserialize_with = "de::number")]
+    pub eps_estimate_average_90_days_ago: Option<f64>,
+    /// Upward EPS revisions over the last 7 days
+    #[serde(default, deserialize_with = "de::amount")]
+    pub eps_estimate_revision_up_trailing_7_days: Option<i64>,
+    /// Downward EPS revisions over the last 7 days
+    #[serde(default, deserialize_with = "de::amount")]
+    pub eps_estimate_revision_down_trailing_7_days: Option<i64>,
+    /// Upward EPS revisions over the last 30 days
+    #[serde(default, deserialize_with = "de::amount")]
+    pub eps_estimate_revision_up_trailing_30_days: Option<i64>,
+    /// Downward EPS revisions over the last 30 days
+    #[serde(default, deserialize_with = "de::amount")]
+    pub eps_estimate_revision_down_trailing_30_days: Option<i64>,
+    /// Average revenue estimate
+    #[serde(default, deserialize_with = "de::amount")]
+    pub revenue_estimate_average: Option<i64>,
+    /// Highest revenue estimate
+    #[serde(default, deserialize_with = "de::amount")]
+    pub revenue_estimate_high: Option<i64>,
+    /// Lowest revenue estimate
+    #[serde(default, deserialize_with = "de::amount")]
+    pub revenue_estimate_low: Option<i64>,
+    /// Number of analysts estimating revenue
+    #[serde(default, deserialize_with = "de::amount")]
+    pub revenue_estimate_analyst_count: Option<i64>,
+}
+
+/// `INCOME_STATEMENT` response
+pub type IncomeStatements = Statements<IncomeStatementReport>;
+/// `BALANCE_SHEET` response
+pub type BalanceSheets = Statements<BalanceSheetReport>;
+/// `CASH_FLOW` response
+pub type CashFlows = Statements<CashFlowReport>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_income_statements() {
+        let body = r#"{
+            "symbol": "IBM",
+            "annualReports": [{
+                "fiscalDateEnding": "2023-12-31",
+                "reportedCurrency": "USD",
+                "grossProfit": "34300000000",
+                "totalRevenue": "61860000000",
+                "investmentIncomeNet": "None",
+                "netIncome": "7502000000"
+            }],
+            "quarterlyReports": [{
+                "fiscalDateEnding": "2024-03-31",
+                "reportedCurrency": "USD",
+                "netIncome": "-1605000000"
+            }]
+        }"#;
+        let statements = IncomeStatements::from_json(body).unwrap();
+
+        let annual = &statements.annual_reports[0];
+        assert_eq!(
+            annual.fiscal_date_ending,
+            NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+        );
+        assert_eq!(annual.gross_profit, Some(34_300_000_000));
+        assert_eq!(annual.investment_income_net, None);
+        assert_eq!(annual.ebitda, None);
+
+        let quarter = &statements.quarterly_reports[0];
+        assert_eq!(quarter.net_income, Some(-1_605_000_000));
+    }
+
+    #[test]
+    fn missing_report_lists_are_empty() {
+        let body =
+            r#"{"symbol": "IBM", "annualReports": [{"fiscalDateEnding": "2023-12-31", "reportedCurrency": "USD"}]}"#;
+        let statements = CashFlows::from_json(body).unwrap();
+        assert_eq!(statements.annual_reports.len(), 1);
+        assert!(statements.quarterly_reports.is_empty());
+
+        let body =
+            r#"{"symbol": "IBM", "quarterlyReports": [{"fiscalDateEnding": "2024-03-31", "reportedCurrency": "USD"}]}"#;
+        let statements = IncomeStatements::from_json(body).unwrap();
+        assert!(statements.annual_reports.is_empty());
+        assert_eq!(statements.quarterly_reports.len(), 1);
+    }
+
+    #[test]
+    fn decodes_earnings_with_fractional_surprise() {
+        let body = r#"{
+            "symbol": "IBM",
+            "annualEarnings": [{"fiscalDateEnding": "2024-12-31", "reportedEPS": "10.33"}],
+            "quarterlyEarnings": [{
+                "fiscalDateEnding": "2024-12-31",
+                "reportedDate": "2025-01-29",
+                "reportedEPS": "3.92",
+                "estimatedEPS": "3.78",
+                "surprise": "0.14",
+                "surprisePercentage": "3.7037",
+                "reportTime": "post-market"
+            }, {
+                "fiscalDateEnding": "2024-09-30",
+                "reportedDate": "2024-10-23",
+                "reportedEPS": "2.3",
+                "estimatedEPS": "None",
+                "surprise": "0",
+                "surprisePercentage": "None",
+                "reportTime": "midday"
+            }]
+        }"#;
+        let earnings = Earnings::from_json(body).unwrap();
+
+        assert_eq!(earnings.annual_earnings[0].reported_eps, Some(10.33));
+        let latest = &earnings.quarterly_earnings[0];
+        assert_eq!(latest.reported_date, NaiveDate::from_ymd_opt(2025, 1, 29));
+        assert!((latest.surprise_percentage.unwrap() - 0.037037).abs() < 1e-9);
+        assert_eq!(latest.report_time, Some(ReportTime::PostMarket));
+
+        let previous = &earnings.quarterly_earnings[1];
+        assert_eq!(previous.estimated_eps, None);
+        assert_eq!(previous.surprise_percentage, None);
+        assert_eq!(previous.report_time, Some(ReportTime::Unknown));
+    }
+
+    #[test]
+    fn finds_estimates_by_horizon() {
+        let body = r#"{"symbol": "IBM", "estimates": [
+            {"date": "2025-12-31", "horizon": "current fiscal year", "eps_estimate_average": "11.0"},
+            {"date": "2026-12-31", "horizon": "next fiscal year", "eps_estimate_average": "11.8",
+             "revenue_estimate_average": "69900000000.00", "eps_estimate_analyst_count": "15.0000"}
+        ]}"#;
+        let estimates = EarningsEstimates::from_json(body).unwrap();
+
+        let next = estimates.horizon("next fiscal year").unwrap();
+        assert_eq!(next.date, NaiveDate::from_ymd_opt(2026, 12, 31).unwrap());
+        assert_eq!(next.eps_estimate_average, Some(11.8));
+        assert_eq!(next.revenue_estimate_average, Some(69_900_000_000));
+        assert_eq!(next.eps_estimate_analyst_count, Some(15));
+        assert!(estimates.horizon("historical fiscal year").is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_amounts_and_surfaces_upstream_errors() {
+        let body = r#"{"symbol": "IBM", "annualReports": [{
+            "fiscalDateEnding": "2023-12-31", "reportedCurrency": "USD", "totalAssets": "lots"
+        }], "quarterlyReports": []}"#;
+        let err = BalanceSheets::from_json(body).unwrap_err();
+        assert_eq!(err.to_string(), "annualReports[0].totalAssets: invalid amount: lots");
+
+        let body = r#"{"Information": "Thank you for using Alpha Vantage!"}"#;
+        let err = CashFlows::from_json(body).unwrap_err();
+        assert!(err.to_string().contains("Thank you"), "{err}");
+    }
+}
@@ -0,0 +1,241 @@
+//! Typed time series responses
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::request::common::{Interval, OutputSize};
+use crate::tool_use::missing_data;
+
+/// A time series: its metadata and bars in ascending time order
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeries {
+    /// Series metadata
+    pub meta: MetaData,
+    /// OHLCV bars, oldest first
+    pub bars: Vec<Bar>,
+}
+
+/// The `Meta Data` block of a time series response
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetaData {
+    /// Description of the series
+    pub information: String,
+    /// Stock symbol
+    pub symbol: String,
+    /// Time of the latest bar
+    pub last_refreshed: NaiveDateTime,
+    /// Bar interval (intraday only)
+    pub interval: Option<Interval>,
+    /// Output size (intraday and daily only)
+    pub output_size: Option<OutputSize>,
+    /// Time zone of the timestamps, e.g. `US/Eastern`
+    pub time_zone: String,
+}
+
+/// One OHLCV bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    /// Start of the bar. Daily, weekly and monthly bars are at midnight.
+    pub timestamp: NaiveDateTime,
+    /// Open price
+    pub open: f64,
+    /// High price
+    pub high: f64,
+    /// Low price
+    pub low: f64,
+    /// Close price
+    pub close: f64,
+    /// Volume
+    pub volume: u64,
+}
+
+impl Bar {
+    /// Calendar date of the bar
+    pub fn date(&self) -> NaiveDate {
+        self.timestamp.date()
+    }
+}
+
+impl TimeSeries {
+    /// Parse a `TIME_SERIES_INTRADAY`, `_DAILY`, `_WEEKLY` or `_MONTHLY` JSON response.
+    pub fn from_json(body: &str) -> Result<Self> {
+        let response: Value = serde_json::from_str(body)?;
+        let object = response
+            .as_object()
+            .ok_or_else(|| missing_data(&response, "Meta Data"))?;
+
+        let meta = object
+            .get("Meta Data")
+            .and_then(Value::as_object)
+            .ok_or_else(|| missing_data(&response, "Meta Data"))?;
+        let meta = MetaData::from_object(meta)?;
+
+        // "Time Series (5min)", "Time Series (Daily)", "Weekly Time Series", ...
+        let series = object
+            .iter()
+            .find(|(key, _)| key.contains("Time Series"))
+            .and_then(|(_, series)| series.as_object())
+            .ok_or_else(|| missing_data(&response, "Time Series"))?;
+
+        let mut bars = series
+            .iter()
+            .map(|(timestamp, values)| Bar::from_entry(timestamp, values))
+            .collect::<Result<Vec<_>>>()?;
+        bars.sort_by_key(|bar| bar.timestamp);
+
+        Ok(Self { meta, bars })
+    }
+}
+
+impl MetaData {
+    fn from_object(meta: &Map<String, Value>) -> Result<Self> {
+        let text = |name: &str| field(meta, name).and_then(Value::as_str);
+        let required = |name: &str| {
+            text(name)
+                .map(str::to_owned)
+                .ok_or_else(|| Error::Custom(format!("Missing '{name}' in time series metadata")))
+        };
+
+        Ok(Self {
+            information: required("Information")?,
+            symbol: required("Symbol")?,
+            last_refreshed: parse_timestamp(&required("Last Refreshed")?)?,
+            interval: text("Interval").map(str::parse).transpose()?,
+            output_size: text("Output Size").map(parse_output_size).transpose()?,
+            time_zone: required("Time Zone")?,
+        })
+    }
+}
+
+impl Bar {
+    fn from_entry(timestamp: &str, values: &Value) -> Result<Self> {
+        let number = |name: &str| {
+            values
+                .as_object()
+                .and_then(|values| field(values, name))
+                .and_then(Value::as_str)
+                .ok_or_else(|| Error::Custom(format!("Missing '{name}' in bar {timestamp}")))
+        };
+        let price = |name: &str| {
+            let value = number(name)?;
+            value
+                .parse::<f64>()
+                .map_err(|_| Error::Custom(format!("Invalid {name} '{value}' in bar {timestamp}")))
+        };
+        let volume = number("volume")?;
+
+        Ok(Self {
+            timestamp: parse_timestamp(timestamp)?,
+            open: price("open")?,
+            high: price("high")?,
+            low: price("low")?,
+            close: price("close")?,
+            volume: volume
+                .parse()
+                .map_err(|_| Error::Custom(format!("Invalid volume '{volume}' in bar {timestamp}")))?,
+        })
+    }
+}
+
+/// Look up a numbered key such as `"3. Last Refreshed"` or `"1. open"` by its name.
+fn field<'v>(object: &'v Map<String, Value>, name: &str) -> Option<&'v Value> {
+    object
+        .iter()
+        .find(|(key, _)| key.split_once(". ").map_or(key.as_str(), |(_, key)| key) == name)
+        .map(|(_, value)| value)
+}
+
+fn parse_timestamp(value: &str) -> Result<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|date| date.and_time(Default::default())))
+        .map_err(|_| Error::Custom(format!("Invalid timestamp: {value}")))
+}
+
+/// Alpha Vantage reports `Compact` or `Full size`.
+fn parse_output_size(value: &str) -> Result<OutputSize> {
+    match value.split_whitespace().next() {
+        Some(size) => size.parse(),
+        None => Err(Error::Custom(format!("Invalid output size: {value}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTRADAY: &str = r#"{
+        "Meta Data": {
+            "1. Information": "Intraday (5min) open, high, low, close prices and volume",
+            "2. Symbol": "IBM",
+            "3. Last Refreshed": "2024-01-05 19:55:00",
+            "4. Interval": "5min",
+            "5. Output Size": "Compact",
+            "6. Time Zone": "US/Eastern"
+        },
+        "Time Series (5min)": {
+            "2024-01-05 19:55:00": {
+                "1. open": "159.1600", "2. high": "159.1600", "3. low": "159.1000",
+                "4. close": "159.1000", "5. volume": "16"
+            },
+            "2024-01-05 19:50:00": {
+                "1. open": "159.1200", "2. high": "159.2000", "3. low": "159.1200",
+                "4. close": "159.1600", "5. volume": "1255"
+            }
+        }
+    }"#;
+
+    #[test]
+    fn decodes_intraday_series() {
+        let series = TimeSeries::from_json(INTRADAY).unwrap();
+
+        assert_eq!(series.meta.symbol, "IBM");
+        assert_eq!(series.meta.interval, Some(Interval::FiveMin));
+        assert_eq!(series.meta.output_size, Some(OutputSize::Compact));
+        assert_eq!(series.meta.time_zone, "US/Eastern");
+        assert_eq!(
+            series.meta.last_refreshed,
+            parse_timestamp("2024-01-05 19:55:00").unwrap()
+        );
+
+        assert_eq!(series.bars.len(), 2);
+        assert_eq!(
+            series.bars[0].timestamp,
+            parse_timestamp("2024-01-05 19:50:00").unwrap()
+        );
+        assert_eq!(series.bars[0].close, 159.16);
+        assert_eq!(series.bars[0].volume, 1255);
+        assert_eq!(series.bars[1].open, 159.16);
+    }
+
+    #[test]
+    fn decodes_weekly_series_without_interval() {
+        let body = r#"{
+            "Meta Data": {
+                "1. Information": "Weekly Prices (open, high, low, close) and Volumes",
+                "2. Symbol": "IBM",
+                "3. Last Refreshed": "2024-01-05",
+                "4. Time Zone": "US/Eastern"
+            },
+            "Weekly Time Series": {
+                "2024-01-05": {
+                    "1. open": "162.8300", "2. high": "163.2900", "3. low": "157.8500",
+                    "4. close": "159.1600", "5. volume": "17897430"
+                }
+            }
+        }"#;
+        let series = TimeSeries::from_json(body).unwrap();
+
+        assert_eq!(series.meta.interval, None);
+        assert_eq!(series.meta.output_size, None);
+        assert_eq!(series.bars[0].date(), NaiveDate::from_ymd_opt(2024, 1, 5).unwrap());
+        assert_eq!(series.bars[0].volume, 17_897_430);
+    }
+
+    #[test]
+    fn surfaces_upstream_errors() {
+        let body = r#"{"Error Message": "Invalid API call."}"#;
+        let err = TimeSeries::from_json(body).unwrap_err();
+        assert!(err.to_string().contains("Invalid API call."), "{err}");
+    }
+}
@@ -11,14 +11,28 @@ pub use raw::Raw;
 pub mod table;
 
 #[cfg(feature = "table")]
-pub use table::Table;
+pub use table::{Table, TypedTable};
+
+#[cfg(feature = "lazy")]
+pub mod lazy;
+
+#[cfg(feature = "lazy")]
+pub use lazy::Lazy;
 
 pub mod decoder;
 
 pub use decoder::Decoder;
 
+#[cfg(feature = "decoder")]
+pub mod json;
+
+#[cfg(feature = "decoder")]
+pub use json::Json;
+
 /// Converts an HTTP response to the desired output type
-pub trait Processor {
+///
+/// Processors are `Send` so that request futures are too.
+pub trait Processor: Send {
     /// The output type this processor produces
     type Output;
 
@@ -3,16 +3,27 @@ use crate::error::Result;
 use crate::processor::Processor;
 use crate::response::Response;
 
+type DecodeFn<T> = Box<dyn Fn(&str) -> Result<T> + Send + Sync>;
+
 /// Decoder processor that uses the decoder crate to parse JSON responses
 pub struct Decoder<T> {
-    decoder_fn: Box<dyn Fn(decoder::Value) -> decoder::Result<T> + Send + Sync>,
+    decoder_fn: DecodeFn<T>,
 }
 
 impl<T> Decoder<T> {
     /// Create a new decoder with the given decoder function
     pub fn new(decoder_fn: impl Fn(decoder::Value) -> decoder::Result<T> + Send + Sync + 'static) -> Self {
+        Self::parse(move |body| {
+            let value: decoder::Value = serde_json::from_str(body)?;
+            Ok(decoder_fn(value)?)
+        })
+    }
+
+    /// Create a decoder from a function parsing the response body, such as
+    /// [`TimeSeries::from_json`](crate::model::TimeSeries::from_json)
+    pub fn parse(parse_fn: impl Fn(&str) -> Result<T> + Send + Sync + 'static) -> Self {
         Self {
-            decoder_fn: Box::new(decoder_fn),
+            decoder_fn: Box::new(parse_fn),
         }
     }
 }
@@ -30,8 +41,38 @@ impl<T> Processor for Decoder<T> {
             });
         }
 
-        let value: decoder::Value = serde_json::from_str(resp.body())?;
-        let decoded = (self.decoder_fn)(value)?;
-        Ok(decoded)
+        (self.decoder_fn)(resp.body())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::AlphaVantage;
+    use crate::mock::{Matcher, MockClient, MockResponse};
+    use crate::rest;
+
+    #[tokio::test]
+    async fn attaches_to_any_builder() {
+        let mock = MockClient::default();
+        mock.on(
+            Matcher::function("GLOBAL_QUOTE"),
+            MockResponse::ok(r#"{"Global Quote": {"01. symbol": "IBM", "05. price": "231.50"}}"#),
+        );
+        let client = AlphaVantage::default().with_client(mock).with_key("test");
+
+        let length = rest::quotes::global_quote(&client, "IBM")
+            .with_processor(Decoder::parse(|body| Ok(body.len())))
+            .get()
+            .await
+            .unwrap();
+        assert!(length > 0);
+
+        let quote = rest::quotes::global_quote(&client, "IBM")
+            .as_decoded()
+            .get()
+            .await
+            .unwrap();
+        assert_eq!((quote.symbol.as_str(), quote.price), ("IBM", 231.5));
     }
 }
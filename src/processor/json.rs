@@ -0,0 +1,107 @@
+//! Serde processor deserializing JSON responses into any `DeserializeOwned` type
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, Result};
+use crate::processor::Processor;
+use crate::response::Response;
+use crate::tool_use::upstream_message;
+
+/// Serde processor that deserializes the response body into `T`
+///
+/// Errors name the JSON path of the field that failed, e.g.
+/// `annualReports[0].totalRevenue: invalid type: ...`. When Alpha Vantage
+/// answers with a rate limit notice or error message instead of data, that
+/// message is returned instead.
+pub struct Json<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Json<T> {
+    /// Create a new JSON processor
+    pub fn new() -> Self {
+        Self { marker: PhantomData }
+    }
+}
+
+impl<T> Default for Json<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DeserializeOwned> Processor for Json<T> {
+    type Output = T;
+
+    fn process<R: Response>(&self, response: Result<R>) -> Result<T> {
+        let resp = response?;
+        if resp.status() != 200 {
+            return Err(Error::ApiError {
+                request_id: resp.request_id().to_owned(),
+                status: resp.status().to_owned(),
+                message: resp.body().to_owned(),
+            });
+        }
+
+        decode(resp.body())
+    }
+}
+
+/// Deserialize `body` into `T`, naming the JSON path of a failing field.
+pub(crate) fn decode<T: DeserializeOwned>(body: &str) -> Result<T> {
+    let deserializer = &mut serde_json::Deserializer::from_str(body);
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        let upstream = serde_json::from_str(body).ok();
+        match upstream.as_ref().and_then(upstream_message) {
+            Some(msg) => Error::Custom(format!("Alpha Vantage API: {msg}")),
+            None => path_error(err),
+        }
+    })
+}
+
+/// Error for a deserialization failure, prefixed with the JSON path where it occurred.
+pub(crate) fn path_error<E: std::fmt::Display>(err: serde_path_to_error::Error<E>) -> Error {
+    Error::Custom(format!("{}: {}", err.path(), err.inner()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Overview {
+        #[serde(rename = "Symbol")]
+        symbol: String,
+        #[serde(rename = "Quarters")]
+        quarters: Vec<Quarter>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Quarter {
+        revenue: u64,
+    }
+
+    #[test]
+    fn errors_name_the_failing_field() {
+        let body = r#"{"Symbol": "IBM", "Quarters": [{"revenue": 1}, {"revenue": "None"}]}"#;
+        let err = decode::<Overview>(body).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Quarters[1].revenue: invalid type"),
+            "{err}"
+        );
+
+        let overview = decode::<Overview>(r#"{"Symbol": "IBM", "Quarters": []}"#).unwrap();
+        assert_eq!(overview.symbol, "IBM");
+    }
+
+    #[test]
+    fn upstream_messages_replace_decode_errors() {
+        let body = r#"{"Note": "API call frequency exceeded"}"#;
+        let err = decode::<Overview>(body).unwrap_err();
+        assert_eq!(err.to_string(), "Alpha Vantage API: API call frequency exceeded");
+    }
+}
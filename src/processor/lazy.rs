@@ -0,0 +1,226 @@
+//! LazyFrame processor using Polars lazy queries
+use crate::error::{Error, Result};
+use crate::processor::table::{response_json, time_zone, typed_columns};
+use crate::processor::{Processor, Table, TypedTable};
+use crate::response::Response;
+use crate::tool_use::{ColumnDef, Schema};
+use polars_core::frame::DataFrame;
+use polars_core::prelude::{PolarsError, PolarsResult};
+use polars_lazy::prelude::*;
+use serde_json::Value;
+use std::any::Any;
+use std::sync::Arc;
+
+/// Table processor that returns a Polars LazyFrame
+///
+/// The frame holds the same table as [`TypedTable`], so filters and projections can be
+/// added to the query before it is collected. Builders' `as_lazyframe()` shape it
+/// for their endpoint, like `as_dataframe()`.
+///
+/// The response is parsed into rows up front, but they are only typed into columns
+/// when the query is collected: a projection types just the selected columns and a
+/// leading `limit` or `head` just the first rows. Filters run on the typed columns.
+/// Without a transform, as from [`Lazy::new`], the response is read like [`Table`]
+/// and the DataFrame is made lazy.
+#[derive(Default)]
+pub struct Lazy {
+    table: TypedTable,
+}
+
+impl Lazy {
+    /// Create a lazy processor for responses holding a top-level array, like
+    /// [`TypedTable::default()`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<TypedTable> for Lazy {
+    fn from(table: TypedTable) -> Self {
+        Self { table }
+    }
+}
+
+impl Processor for Lazy {
+    type Output = LazyFrame;
+
+    fn process<R: Response>(&self, response: Result<R>) -> Result<LazyFrame> {
+        let Some(transform) = self.table.transform() else {
+            return Table.process(response).map(IntoLazy::lazy);
+        };
+        let (rows, metadata, schema) = transform(response_json(response)?)?;
+        RowScan::new(rows, metadata.as_ref(), schema).into_lazy()
+    }
+}
+
+/// A transform's rows, typed into columns when the query scans them
+struct RowScan {
+    rows: Vec<Value>,
+    columns: Schema,
+    time_zone: Option<String>,
+}
+
+impl RowScan {
+    fn new(rows: Value, metadata: Option<&Value>, schema: Schema) -> Self {
+        let rows = match rows {
+            Value::Array(rows) => rows,
+            _ => Vec::new(),
+        };
+        // Like `typed_dataframe`, columns no row has are left out.
+        let columns = schema
+            .into_iter()
+            .filter(|column| rows.iter().any(|row| row.get(&column.name).is_some()))
+            .collect();
+        let time_zone = metadata.and_then(time_zone).map(str::to_owned);
+        Self {
+            rows,
+            columns,
+            time_zone,
+        }
+    }
+
+    fn into_lazy(self) -> Result<LazyFrame> {
+        let schema = typed_columns(&[], &self.columns, self.time_zone.as_deref())?
+            .schema()
+            .clone();
+        let args = ScanArgsAnonymous {
+            schema: Some(schema),
+            name: "ALPHA VANTAGE ROWS",
+            ..Default::default()
+        };
+        LazyFrame::anonymous_scan(Arc::new(self), args)
+            .map_err(|e| Error::Custom(format!("Failed to build lazy table: {e}")))
+    }
+}
+
+impl AnonymousScan for RowScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, args: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let rows = &self.rows[..args.n_rows.map_or(self.rows.len(), |n| n.min(self.rows.len()))];
+        let columns: Vec<&ColumnDef> = match &args.with_columns {
+            Some(names) => names
+                .iter()
+                .filter_map(|name| self.columns.iter().find(|column| column.name == name.as_str()))
+                .collect(),
+            None => self.columns.iter().collect(),
+        };
+        typed_columns(rows, columns, self.time_zone.as_deref())
+            .map_err(|e| PolarsError::ComputeError(e.to_string().into()))
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+}
+
+/// Stack per-symbol frames into one, tagged with a leading `symbol` column.
+///
+/// Frames that already have a `symbol` column, such as quotes, keep its values.
+/// Frames may have different columns; those a frame lacks are null in its rows.
+pub fn concat_symbols<S: Into<String>>(frames: impl IntoIterator<Item = (S, LazyFrame)>) -> Result<LazyFrame> {
+    let tagged = frames
+        .into_iter()
+        .map(|(symbol, mut frame)| {
+            let schema = frame
+                .collect_schema()
+                .map_err(|e| Error::Custom(format!("Failed to read frame schema: {e}")))?;
+            Ok(if schema.contains("symbol") {
+                frame.select([col("symbol"), all().exclude_cols(["symbol"]).as_expr()])
+            } else {
+                frame.select([lit(symbol.into()).alias("symbol"), all().as_expr()])
+            })
+        })
+        .collect::<Result<Vec<LazyFrame>>>()?;
+    if tagged.is_empty() {
+        return Ok(DataFrame::empty().lazy());
+    }
+    concat_lf_diagonal(tagged, UnionArgs::default())
+        .map_err(|e| Error::Custom(format!("Failed to concatenate frames: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::AlphaVantage;
+    use crate::mock::{Matcher, MockClient, MockResponse};
+    use crate::request::HttpResponse;
+    use crate::rest;
+    use crate::tool_use::daily_table;
+
+    fn daily(close: &str) -> LazyFrame {
+        let body = format!(
+            r#"{{"Meta Data": {{"2. Symbol": "X"}},
+                 "Time Series (Daily)": {{"2025-01-08": {{"4. close": "{close}", "5. volume": "100"}}}}}}"#
+        );
+        Lazy::from(TypedTable::with_transform(daily_table))
+            .process(Ok(HttpResponse::new(200, body, None)))
+            .unwrap()
+    }
+
+    #[test]
+    fn types_only_the_scanned_columns_and_rows() {
+        let body = r#"{"Meta Data": {"2. Symbol": "X"},
+            "Time Series (Daily)": {
+                "2025-01-08": {"4. close": "242.7", "5. volume": "100"},
+                "2025-01-07": {"4. close": "223.2", "5. volume": "200"},
+                "2025-01-06": {"4. close": "None", "5. volume": "300"}}}"#;
+        let frame = Lazy::from(TypedTable::with_transform(daily_table))
+            .process(Ok(HttpResponse::new(200, body.to_string(), None)))
+            .unwrap();
+
+        let plan = frame.clone().select([col("close")]).limit(2).explain(true).unwrap();
+        assert!(plan.contains("PROJECT 1/6 COLUMNS"), "{plan}");
+        assert!(plan.contains("SLICE: Positive { offset: 0, len: 2 }"), "{plan}");
+        let df = frame.clone().select([col("close")]).limit(2).collect().unwrap();
+        assert_eq!(df.shape(), (2, 1));
+        assert_eq!(df.column("close").unwrap().f64().unwrap().get(1), Some(223.2));
+
+        let df = frame.filter(col("close").is_null()).collect().unwrap();
+        assert_eq!(df.height(), 1);
+        assert_eq!(df.column("volume").unwrap().i64().unwrap().get(0), Some(300));
+    }
+
+    #[test]
+    fn concatenates_symbols_lazily() {
+        let frames = [("IBM", daily("223.2")), ("AAPL", daily("242.7"))];
+        let df = concat_symbols(frames)
+            .unwrap()
+            .filter(col("close").gt(lit(230.0)))
+            .select([col("symbol"), col("close")])
+            .collect()
+            .unwrap();
+        assert_eq!(df.height(), 1);
+        let symbols = df.column("symbol").unwrap().str().unwrap();
+        assert_eq!(symbols.get(0), Some("AAPL"));
+
+        let all = concat_symbols(Vec::<(String, LazyFrame)>::new()).unwrap();
+        assert_eq!(all.collect().unwrap().height(), 0);
+    }
+
+    #[tokio::test]
+    async fn keeps_existing_symbol_columns() {
+        let mock = MockClient::default();
+        mock.on(
+            Matcher::function("GLOBAL_QUOTE"),
+            MockResponse::ok(r#"{"Global Quote": {"01. symbol": "IBM", "05. price": "223.2000"}}"#),
+        );
+        let client = AlphaVantage::default().with_client(mock.clone()).with_key("test");
+        let quote = rest::quotes::global_quote(&client, "ibm")
+            .as_lazyframe()
+            .get()
+            .await
+            .unwrap();
+
+        let df = concat_symbols([("ibm", quote)]).unwrap().collect().unwrap();
+        let names: Vec<_> = df.get_column_names().into_iter().map(|name| name.as_str()).collect();
+        assert_eq!(names, ["symbol", "price"]);
+        assert_eq!(df.column("symbol").unwrap().str().unwrap().get(0), Some("IBM"));
+    }
+}
@@ -2,11 +2,22 @@
 use crate::error::Result;
 use crate::processor::Processor;
 use crate::response::Response;
+use crate::tool_use::{ColumnDef, Schema, coerce_rows, missing_data};
+use chrono::{NaiveDate, NaiveDateTime, TimeZone as _};
 use polars_core::frame::DataFrame;
+use polars_core::prelude::*;
 use polars_io::prelude::*;
+use serde_json::Value;
 use std::io::Cursor;
 
+/// Extracts the rows, metadata and column schema of one endpoint's response
+pub(crate) type Transform = Box<dyn Fn(Value) -> Result<(Value, Option<Value>, Schema)> + Send + Sync>;
+
 /// Table processor that converts JSON responses to Polars DataFrames
+///
+/// Reads the first top-level array of the response (`estimates`, `results`,
+/// `annualReports`, `quarterlyReports` or `data`) as it is. Builders'
+/// `as_dataframe()` use [`TypedTable`] instead.
 pub struct Table;
 
 impl Processor for Table {
@@ -47,15 +58,404 @@ impl Processor for Table {
             )));
         }
 
-        // Allow empty arrays - we must preserve original API responses per Alpha Vantage guidelines
-        let json_bytes = serde_json::to_vec(data)?;
-        let json_preview = String::from_utf8_lossy(&json_bytes[..json_bytes.len().min(200)]).to_string();
-        let df = JsonReader::new(Cursor::new(json_bytes)).finish().map_err(|e| {
-            crate::error::Error::Custom(format!(
-                "Failed to parse JSON as DataFrame: {}. Data preview: {}",
-                e, json_preview
-            ))
-        })?;
-        Ok(df)
+        to_dataframe(data)
+    }
+}
+
+/// Table processor that shapes each endpoint's response into typed columns
+///
+/// Builders' `as_dataframe()` return it: one row per bar, report or estimate,
+/// with the columns of the matching `tool_use` tool. Columns take the dtype of
+/// their schema entry: `"number"` is `Float64`, `"integer"` is `Int64`, `"date"`
+/// is `Date` and `"datetime"` is a millisecond `Datetime` in the response's
+/// `Time Zone`, if it names one. `"None"`, `"-"` and empty strings are null.
+/// The default processor reads the response like [`Table`].
+#[derive(Default)]
+pub struct TypedTable {
+    transform: Option<Transform>,
+}
+
+impl TypedTable {
+    /// Create a table processor that builds its rows with `transform`
+    pub(crate) fn with_transform(
+        transform: impl Fn(Value) -> Result<(Value, Option<Value>, Schema)> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            transform: Some(Box::new(transform)),
+        }
+    }
+
+    /// Build the table along with the response metadata it is read from, such
+    /// as its `Meta Data` object
+    pub(crate) fn table<R: Response>(&self, response: Result<R>) -> Result<(DataFrame, Option<Value>)> {
+        let Some(transform) = &self.transform else {
+            return Table.process(response).map(|df| (df, None));
+        };
+        let (rows, metadata, schema) = transform(response_json(response)?)?;
+        let df = typed_dataframe(&rows, metadata.as_ref(), &schema)?;
+        Ok((df, metadata))
+    }
+
+    /// The transform building this table's rows, if it has one
+    pub(crate) fn transform(&self) -> Option<&Transform> {
+        self.transform.as_ref()
+    }
+}
+
+/// Parse a successful response's body, or return its API error
+pub(crate) fn response_json<R: Response>(response: Result<R>) -> Result<Value> {
+    let resp = response?;
+    if resp.status() != 200 {
+        return Err(crate::error::Error::ApiError {
+            request_id: resp.request_id().to_owned(),
+            status: resp.status().to_owned(),
+            message: resp.body().to_owned(),
+        });
+    }
+    Ok(serde_json::from_str(resp.body())?)
+}
+
+impl Processor for TypedTable {
+    type Output = DataFrame;
+
+    fn process<R: Response>(&self, response: Result<R>) -> Result<DataFrame> {
+        self.table(response).map(|(df, _)| df)
+    }
+}
+
+/// Read rows into a DataFrame with the columns, in order, and dtypes of `schema`.
+///
+/// Datetime columns take the `Time Zone` named in `metadata`, if any.
+pub(crate) fn typed_dataframe(rows: &Value, metadata: Option<&Value>, schema: &Schema) -> Result<DataFrame> {
+    let rows = rows.as_array().map(Vec::as_slice).unwrap_or_default();
+    // Columns are built straight from the rows; those no row has are left out.
+    let columns = schema
+        .iter()
+        .filter(|column| rows.iter().any(|row| row.get(&column.name).is_some()));
+    typed_columns(rows, columns, metadata.and_then(time_zone))
+}
+
+/// Read rows into a DataFrame of the given columns, in order, including those no row has.
+pub(crate) fn typed_columns<'c>(
+    rows: &[Value],
+    columns: impl IntoIterator<Item = &'c ColumnDef>,
+    time_zone: Option<&str>,
+) -> Result<DataFrame> {
+    let columns = columns
+        .into_iter()
+        .map(|column| {
+            let values = rows
+                .iter()
+                .map(|row| row.get(&column.name).filter(|value| !value.is_null()));
+            typed_column(column.name.as_str().into(), values, &column.dtype, time_zone)
+                .map_err(|e| crate::error::Error::Custom(format!("Failed to type column {}: {e}", column.name)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    DataFrame::new(columns).map_err(|e| crate::error::Error::Custom(format!("Failed to build table: {e}")))
+}
+
+/// Read a JSON array of row objects into a DataFrame.
+fn to_dataframe(data: &Value) -> Result<DataFrame> {
+    // Allow empty arrays - we must preserve original API responses per Alpha Vantage guidelines
+    let json_bytes = serde_json::to_vec(data)?;
+    let json_preview = String::from_utf8_lossy(&json_bytes[..json_bytes.len().min(200)]).to_string();
+    let df = JsonReader::new(Cursor::new(json_bytes)).finish().map_err(|e| {
+        crate::error::Error::Custom(format!(
+            "Failed to parse JSON as DataFrame: {}. Data preview: {}",
+            e, json_preview
+        ))
+    })?;
+    Ok(df)
+}
+
+/// The `Time Zone` named in a response's metadata, e.g. `"6. Time Zone": "US/Eastern"`
+pub(crate) fn time_zone(metadata: &Value) -> Option<&str> {
+    metadata
+        .as_object()?
+        .iter()
+        .find(|(key, _)| key.ends_with("Time Zone"))
+        .and_then(|(_, value)| value.as_str())
+}
+
+/// Build a column of the polars type of `dtype` from coerced JSON values.
+fn typed_column<'v>(
+    name: PlSmallStr,
+    values: impl Iterator<Item = Option<&'v Value>>,
+    dtype: &str,
+    time_zone: Option<&str>,
+) -> PolarsResult<Column> {
+    match dtype {
+        "number" => {
+            let numbers: Float64Chunked = values.map(|value| value.and_then(Value::as_f64)).collect();
+            Ok(numbers.with_name(name).into_column())
+        }
+        "integer" => {
+            let integers: Int64Chunked = values.map(|value| value.and_then(Value::as_i64)).collect();
+            Ok(integers.with_name(name).into_column())
+        }
+        "date" => {
+            let days: Int32Chunked = values
+                .map(|value| {
+                    value
+                        .and_then(Value::as_str)
+                        .and_then(parse_date)
+                        .map(|date| (date - NaiveDate::default()).num_days() as i32)
+                })
+                .collect();
+            Ok(days.with_name(name).into_date().into_column())
+        }
+        "datetime" => {
+            let time_zone = polars_core::prelude::TimeZone::opt_try_new(time_zone)?;
+            let tz = time_zone.as_ref().map(|tz| tz.to_chrono()).transpose()?;
+            // Alpha Vantage timestamps are local to the time zone; store them as UTC instants.
+            let millis: Int64Chunked = values
+                .map(|value| {
+                    let local = value.and_then(Value::as_str).and_then(parse_datetime)?;
+                    match tz {
+                        Some(tz) => tz
+                            .from_local_datetime(&local)
+                            .earliest()
+                            .map(|at| at.timestamp_millis()),
+                        None => Some(local.and_utc().timestamp_millis()),
+                    }
+                })
+                .collect();
+            Ok(millis
+                .with_name(name)
+                .into_datetime(TimeUnit::Milliseconds, time_zone)
+                .into_column())
+        }
+        _ => {
+            let text: StringChunked = values
+                .map(|value| {
+                    value.map(|value| match value {
+                        Value::String(text) => text.clone(),
+                        other => other.to_string(),
+                    })
+                })
+                .collect();
+            Ok(text.with_name(name).into_column())
+        }
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .or_else(|| parse_date(value).and_then(|date| date.and_hms_opt(0, 0, 0)))
+}
+
+/// Transform a `GLOBAL_QUOTE` response into a single row
+pub(crate) fn global_quote_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+    let quote = response
+        .get("Global Quote")
+        .and_then(Value::as_object)
+        .filter(|quote| !quote.is_empty())
+        .ok_or_else(|| missing_data(&response, "Global Quote"))?;
+
+    // "05. price" -> "price", "10. change percent" -> "change_percent"
+    let row: serde_json::Map<String, Value> = quote
+        .iter()
+        .map(|(key, value)| {
+            let name = key.split_once(". ").map_or(key.as_str(), |(_, name)| name);
+            (name.replace(' ', "_"), value.clone())
+        })
+        .collect();
+
+    let schema = columns(&[
+        ("symbol", "Symbol", "string"),
+        ("open", "Open", "number"),
+        ("high", "High", "number"),
+        ("low", "Low", "number"),
+        ("price", "Price", "number"),
+        ("volume", "Volume", "integer"),
+        ("latest_trading_day", "Latest Trading Day", "date"),
+        ("previous_close", "Previous Close", "number"),
+        ("change", "Change", "number"),
+        ("change_percent", "Change %", "number"),
+    ]);
+
+    Ok((coerce_rows(vec![Value::Object(row)], &schema), None, schema))
+}
+
+/// Transform a `REALTIME_BULK_QUOTES` response into one row per symbol
+pub(crate) fn bulk_quotes_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+    let data = response
+        .get("data")
+        .and_then(Value::as_array)
+        .cloned()
+        .ok_or_else(|| missing_data(&response, "data"))?;
+
+    let schema = columns(&[
+        ("symbol", "Symbol", "string"),
+        ("timestamp", "Timestamp", "datetime"),
+        ("open", "Open", "number"),
+        ("high", "High", "number"),
+        ("low", "Low", "number"),
+        ("close", "Close", "number"),
+        ("volume", "Volume", "integer"),
+        ("previous_close", "Previous Close", "number"),
+        ("change", "Change", "number"),
+        ("change_percent", "Change %", "number"),
+        ("extended_hours_quote", "Extended Hours Quote", "number"),
+        ("extended_hours_change", "Extended Hours Change", "number"),
+        ("extended_hours_change_percent", "Extended Hours Change %", "number"),
+    ]);
+
+    Ok((coerce_rows(data, &schema), None, schema))
+}
+
+fn columns(columns: &[(&str, &str, &str)]) -> Schema {
+    columns
+        .iter()
+        .map(|(name, alias, dtype)| ColumnDef {
+            name: name.to_string(),
+            alias: alias.to_string(),
+            dtype: dtype.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::HttpResponse;
+    use crate::request::common::Period;
+    use crate::tool_use::{
+        daily_table, earnings_table, income_statement_table, intraday_table, overview_table, wide_statement,
+    };
+
+    fn table(processor: impl Processor<Output = DataFrame>, body: &str) -> Result<DataFrame> {
+        processor.process(Ok(HttpResponse::new(200, body.to_string(), None)))
+    }
+
+    fn names(df: &DataFrame) -> Vec<&str> {
+        df.get_column_names().into_iter().map(|name| name.as_str()).collect()
+    }
+
+    #[test]
+    fn shapes_keyed_time_series_and_single_objects() {
+        let daily = r#"{
+            "Meta Data": {"2. Symbol": "IBM"},
+            "Time Series (Daily)": {
+                "2025-01-07": {"1. open": "224.5", "2. high": "226.7", "3. low": "222.3",
+                               "4. close": "223.2", "5. volume": "3458601"},
+                "2025-01-08": {"1. open": "222.9", "2. high": "223.7", "3. low": "220.8",
+                               "4. close": "223.2", "5. volume": "2796447"}
+            }
+        }"#;
+        let df = table(TypedTable::with_transform(daily_table), daily).unwrap();
+        assert_eq!(names(&df), ["date", "open", "high", "low", "close", "volume"]);
+        assert_eq!(df.height(), 2);
+
+        let overview = r#"{"Symbol": "IBM", "Name": "International Business Machines", "Sector": "TECHNOLOGY"}"#;
+        let df = table(TypedTable::with_transform(overview_table), overview).unwrap();
+        assert_eq!(df.height(), 1);
+        assert_eq!(&names(&df)[..2], ["Symbol", "Name"]);
+
+        let quote = r#"{"Global Quote": {"01. symbol": "IBM", "05. price": "231.50", "10. change percent": "0.5%"}}"#;
+        let df = table(TypedTable::with_transform(global_quote_table), quote).unwrap();
+        assert_eq!(names(&df), ["symbol", "price", "change_percent"]);
+    }
+
+    #[test]
+    fn columns_take_schema_dtypes() {
+        let intraday = r#"{
+            "Meta Data": {"2. Symbol": "IBM", "6. Time Zone": "US/Eastern"},
+            "Time Series (5min)": {
+                "2025-01-07 16:00:00": {"1. open": "224.5", "2. high": "None", "3. low": "-",
+                                        "4. close": "223", "5. volume": "3458601"}
+            }
+        }"#;
+        let df = table(
+            TypedTable::with_transform(|resp| intraday_table(resp, "5min")),
+            intraday,
+        )
+        .unwrap();
+        let dtypes: Vec<_> = df.dtypes();
+        assert_eq!(
+            dtypes[1..],
+            [
+                DataType::Float64,
+                DataType::Float64,
+                DataType::Float64,
+                DataType::Float64,
+                DataType::Int64
+            ]
+        );
+        assert_eq!(df.column("high").unwrap().null_count(), 1);
+        assert_eq!(df.column("low").unwrap().null_count(), 1);
+
+        // 16:00 in New York is 21:00 UTC in January
+        let timestamp = df.column("timestamp").unwrap();
+        assert_eq!(
+            timestamp.dtype(),
+            &DataType::Datetime(
+                TimeUnit::Milliseconds,
+                polars_core::prelude::TimeZone::opt_try_new(Some("US/Eastern")).unwrap()
+            )
+        );
+        let millis = timestamp.datetime().unwrap().phys.get(0).unwrap();
+        assert_eq!(millis, 1_736_283_600_000);
+
+        let df = table(
+            TypedTable::with_transform(earnings_table),
+            r#"{"annualEarnings": [{"fiscalDateEnding": "2024-12-31"}]}"#,
+        )
+        .unwrap();
+        let dates = df.column("fiscal_date_ending").unwrap();
+        assert_eq!(dates.dtype(), &DataType::Date);
+        assert_eq!(dates.date().unwrap().phys.get(0), Some(20088));
+        assert_eq!(df.column("reported_eps").unwrap().dtype(), &DataType::Float64);
+    }
+
+    #[test]
+    fn wide_statements_keep_period_order() {
+        let income = r#"{
+            "symbol": "IBM",
+            "annualReports": [{"fiscalDateEnding": "2023-12-31", "totalRevenue": "61860000000"},
+                              {"fiscalDateEnding": "2024-12-31", "totalRevenue": "62753000000"},
+                              {"fiscalDateEnding": "2023-12-31", "totalRevenue": "61000000000"}]
+        }"#;
+        let wide = TypedTable::with_transform(|response| {
+            income_statement_table(response).map(|table| wide_statement(table, Period::Annual))
+        });
+        let df = table(wide, income).unwrap();
+        assert_eq!(names(&df), ["line_item", "2024-12-31", "2023-12-31"]);
+        assert_eq!(df.column("2024-12-31").unwrap().dtype(), &DataType::Float64);
+        let revenue = df.column("2023-12-31").unwrap().f64().unwrap().get(0);
+        assert_eq!(revenue, Some(61_860_000_000.0));
+    }
+
+    #[test]
+    fn earnings_include_annual_and_quarterly_rows() {
+        let earnings = r#"{
+            "symbol": "IBM",
+            "annualEarnings": [{"fiscalDateEnding": "2024-12-31", "reportedEPS": "10.33"}],
+            "quarterlyEarnings": [{"fiscalDateEnding": "2024-12-31", "reportedEPS": "3.92",
+                                   "estimatedEPS": "3.78", "reportedDate": "2025-01-29",
+                                   "surprise": "0.14", "surprisePercentage": "3.7"}]
+        }"#;
+        let df = table(TypedTable::with_transform(earnings_table), earnings).unwrap();
+        assert_eq!(df.height(), 2);
+        let periods = df.column("period_type").unwrap().str().unwrap();
+        assert_eq!(
+            periods.into_iter().flatten().collect::<Vec<_>>(),
+            ["annual", "quarterly"]
+        );
+
+        // Without a transform, only top-level arrays are understood
+        assert!(table(Table, earnings).is_err());
+    }
+
+    #[test]
+    fn transforms_surface_upstream_messages() {
+        let body = r#"{"Information": "The **demo** API key is for demo purposes only."}"#;
+        let err = table(TypedTable::with_transform(daily_table), body).unwrap_err();
+        assert!(err.to_string().contains("demo purposes"), "{err}");
     }
 }
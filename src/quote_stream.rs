@@ -0,0 +1,592 @@
+//! Polling stream of realtime quotes
+//!
+//! [`QuoteStream`] polls `GLOBAL_QUOTE` (one request per symbol) or the premium
+//! `REALTIME_BULK_QUOTES` endpoint at a fixed cadence and yields only the quotes
+//! that changed since the previous poll. Outside [`MarketHours`] it sleeps until
+//! the market opens. Symbols can be added and removed while it runs, through the
+//! stream itself or a cloned [`Subscriptions`] handle.
+//!
+//! Polling is driven by the consumer: the next poll starts only once every change
+//! from the previous one has been taken from the stream, so a slow consumer delays
+//! polls instead of letting quotes pile up. Ticks missed this way are skipped.
+//! Failed requests are yielded as errors and polling carries on.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use alphav::AlphaVantage;
+//! use alphav::quote_stream::{QuoteSource, QuoteStream};
+//! use futures_util::StreamExt;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let client = AlphaVantage::default().with_key("your_api_key");
+//! let mut quotes = QuoteStream::builder(&client, ["IBM", "AAPL"])
+//!     .source(QuoteSource::Bulk)
+//!     .interval(Duration::from_secs(15))
+//!     .start();
+//!
+//! while let Some(quote) = quotes.next().await {
+//!     match quote {
+//!         Ok(quote) => println!("{} {}", quote.symbol, quote.price),
+//!         Err(e) => eprintln!("poll failed: {e}"),
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike, Utc, Weekday};
+use futures_util::stream::{self, Stream};
+use serde_json::Value;
+use tokio::time::Instant;
+
+use crate::client::ClientRef;
+use crate::error::Result;
+use crate::request::Request;
+use crate::rest;
+use crate::tool_use::missing_data;
+
+/// Default time between polls
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Endpoint a [`QuoteStream`] polls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteSource {
+    /// `GLOBAL_QUOTE`, one request per symbol
+    #[default]
+    GlobalQuote,
+    /// `REALTIME_BULK_QUOTES`, one request per 100 symbols (premium)
+    Bulk,
+}
+
+/// Latest quote of a symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote {
+    /// Stock symbol
+    pub symbol: String,
+    /// Latest price
+    pub price: f64,
+    /// Session open
+    pub open: Option<f64>,
+    /// Session high
+    pub high: Option<f64>,
+    /// Session low
+    pub low: Option<f64>,
+    /// Session volume
+    pub volume: Option<f64>,
+    /// Previous session close
+    pub previous_close: Option<f64>,
+    /// Change from the previous close
+    pub change: Option<f64>,
+    /// Change from the previous close, in percent
+    pub change_percent: Option<f64>,
+    /// Time of the quote (bulk quotes) or latest trading day (global quote)
+    pub timestamp: Option<String>,
+}
+
+impl Quote {
+    /// Parse a `GLOBAL_QUOTE` JSON response.
+    pub fn from_global_quote(body: &str) -> Result<Self> {
+        let response: Value = serde_json::from_str(body)?;
+        let quote = response
+            .get("Global Quote")
+            .filter(|q| q.as_object().is_some_and(|q| !q.is_empty()))
+            .ok_or_else(|| missing_data(&response, "Global Quote"))?;
+        let field = |key: &str| quote.get(key);
+
+        Ok(Self {
+            symbol: text(field("01. symbol")).ok_or_else(|| missing_data(&response, "01. symbol"))?,
+            price: number(field("05. price")).ok_or_else(|| missing_data(&response, "05. price"))?,
+            open: number(field("02. open")),
+            high: number(field("03. high")),
+            low: number(field("04. low")),
+            volume: number(field("06. volume")),
+            previous_close: number(field("08. previous close")),
+            change: number(field("09. change")),
+            change_percent: number(field("10. change percent")),
+            timestamp: text(field("07. latest trading day")),
+        })
+    }
+
+    /// Parse a `REALTIME_BULK_QUOTES` JSON response. Entries without a symbol or
+    /// price are skipped.
+    pub fn from_bulk_quotes(body: &str) -> Result<Vec<Self>> {
+        let response: Value = serde_json::from_str(body)?;
+        let data = response
+            .get("data")
+            .and_then(Value::as_array)
+            .ok_or_else(|| missing_data(&response, "data"))?;
+
+        Ok(data
+            .iter()
+            .filter_map(|quote| {
+                let field = |key: &str| quote.get(key);
+                Some(Self {
+                    symbol: text(field("symbol"))?,
+                    price: number(field("close"))?,
+                    open: number(field("open")),
+                    high: number(field("high")),
+                    low: number(field("low")),
+                    volume: number(field("volume")),
+                    previous_close: number(field("previous_close")),
+                    change: number(field("change")),
+                    change_percent: number(field("change_percent")),
+                    timestamp: text(field("timestamp")),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Read a string field.
+fn text(value: Option<&Value>) -> Option<String> {
+    value?.as_str().map(str::to_string)
+}
+
+/// Read a number sent as a JSON number or a string such as `"1.25"` or `"0.5%"`.
+fn number(value: Option<&Value>) -> Option<f64> {
+    match value? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().trim_end_matches('%').parse().ok(),
+        _ => None,
+    }
+}
+
+/// Regular trading session in US Eastern time, Monday to Friday
+///
+/// Exchange holidays are not taken into account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketHours {
+    open: u32,
+    close: u32,
+}
+
+impl MarketHours {
+    /// NYSE and Nasdaq regular session, 9:30 to 16:00
+    pub const US_EQUITIES: MarketHours = MarketHours {
+        open: 9 * 60 + 30,
+        close: 16 * 60,
+    };
+
+    /// Session from `open` to `close`, as `(hour, minute)` in US Eastern time.
+    pub fn new(open: (u32, u32), close: (u32, u32)) -> Self {
+        Self {
+            open: open.0 * 60 + open.1,
+            close: close.0 * 60 + close.1,
+        }
+    }
+
+    /// Whether the session is open at `at`.
+    pub fn is_open(&self, at: SystemTime) -> bool {
+        let local = eastern(DateTime::from(at));
+        let minute = local.hour() * 60 + local.minute();
+        is_weekday(local.date()) && (self.open..self.close).contains(&minute)
+    }
+
+    /// When the session next opens; `at` itself if it is open then.
+    pub fn next_open(&self, at: SystemTime) -> SystemTime {
+        if self.is_open(at) {
+            return at;
+        }
+        let now = DateTime::<Utc>::from(at);
+        eastern(now)
+            .date()
+            .iter_days()
+            .take(8)
+            .filter(|&day| is_weekday(day))
+            .map(|day| {
+                let local = day.and_time(NaiveTime::MIN) + TimeDelta::minutes(i64::from(self.open));
+                // Sessions never start near the 2am switch, so the standard-time guess decides DST
+                let standard = (local + TimeDelta::hours(5)).and_utc();
+                if is_dst(standard) {
+                    standard - TimeDelta::hours(1)
+                } else {
+                    standard
+                }
+            })
+            .find(|&open| open > now)
+            .map_or(at, SystemTime::from)
+    }
+}
+
+impl Default for MarketHours {
+    fn default() -> Self {
+        Self::US_EQUITIES
+    }
+}
+
+/// Wall-clock time in US Eastern time.
+///
+/// This applies fixed offsets (UTC-5, or UTC-4 under [`is_dst`]) rather than a time
+/// zone database, so it follows the daylight saving rules in force since 2007 and
+/// can be an hour off for earlier dates.
+fn eastern(utc: DateTime<Utc>) -> NaiveDateTime {
+    let offset = if is_dst(utc) { 4 } else { 5 };
+    (utc - TimeDelta::hours(offset)).naive_utc()
+}
+
+/// US daylight saving time under the rules in force since 2007: second Sunday of
+/// March, 2:00 EST (7:00 UTC), to first Sunday of November, 2:00 EDT (6:00 UTC).
+fn is_dst(utc: DateTime<Utc>) -> bool {
+    let switch = |month, n, hour| {
+        NaiveDate::from_weekday_of_month_opt(utc.year(), month, Weekday::Sun, n)
+            .and_then(|day| day.and_hms_opt(hour, 0, 0))
+            .map(|at| at.and_utc())
+    };
+    match (switch(3, 2, 7), switch(11, 1, 6)) {
+        (Some(start), Some(end)) => (start..end).contains(&utc),
+        _ => false,
+    }
+}
+
+fn is_weekday(day: NaiveDate) -> bool {
+    day.weekday().num_days_from_monday() < 5
+}
+
+/// Shared, changeable set of symbols a [`QuoteStream`] polls
+///
+/// Symbols are upper-cased. Changes apply from the next poll.
+#[derive(Debug, Clone, Default)]
+pub struct Subscriptions {
+    symbols: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl Subscriptions {
+    /// Start polling `symbols`.
+    pub fn subscribe<S: Into<String>>(&self, symbols: impl IntoIterator<Item = S>) {
+        let mut current = self.lock();
+        current.extend(symbols.into_iter().map(|s| s.into().to_ascii_uppercase()));
+    }
+
+    /// Stop polling `symbols`.
+    pub fn unsubscribe<S: Into<String>>(&self, symbols: impl IntoIterator<Item = S>) {
+        let mut current = self.lock();
+        for symbol in symbols {
+            current.remove(&symbol.into().to_ascii_uppercase());
+        }
+    }
+
+    /// Symbols currently polled, in alphabetical order.
+    pub fn symbols(&self) -> Vec<String> {
+        self.lock().iter().cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeSet<String>> {
+        self.symbols.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Configures a [`QuoteStream`]
+pub struct QuoteStreamBuilder<'a, Client: Request> {
+    client: ClientRef<'a, Client>,
+    subscriptions: Subscriptions,
+    source: QuoteSource,
+    interval: Duration,
+    market_hours: Option<MarketHours>,
+}
+
+impl<'a, Client: Request> QuoteStreamBuilder<'a, Client> {
+    /// Set the endpoint to poll. Defaults to [`QuoteSource::GlobalQuote`].
+    pub fn source(mut self, source: QuoteSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Set the time between the starts of consecutive polls. Defaults to [`DEFAULT_INTERVAL`].
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the session outside which polling pauses, or `None` to poll around
+    /// the clock. Defaults to [`MarketHours::US_EQUITIES`].
+    pub fn market_hours(mut self, hours: Option<MarketHours>) -> Self {
+        self.market_hours = hours;
+        self
+    }
+
+    /// Create the stream. Nothing is requested until it is polled.
+    pub fn start(self) -> QuoteStream<'a> {
+        let subscriptions = self.subscriptions.clone();
+        let poller = Poller {
+            client: self.client,
+            subscriptions: self.subscriptions,
+            source: self.source,
+            interval: self.interval,
+            market_hours: self.market_hours,
+            last: HashMap::new(),
+            pending: VecDeque::new(),
+            next_poll: None,
+        };
+        let inner = stream::unfold(poller, |mut poller| async move {
+            let item = poller.next().await;
+            Some((item, poller))
+        });
+
+        QuoteStream {
+            subscriptions,
+            inner: Box::pin(inner),
+        }
+    }
+}
+
+/// Endless stream of changed quotes, created with [`QuoteStream::builder`]
+pub struct QuoteStream<'a> {
+    subscriptions: Subscriptions,
+    inner: Pin<Box<dyn Stream<Item = Result<Quote>> + Send + 'a>>,
+}
+
+impl<'a> QuoteStream<'a> {
+    /// Start configuring a stream polling `symbols` through `client`.
+    pub fn builder<Client, S>(
+        client: impl Into<ClientRef<'a, Client>>,
+        symbols: impl IntoIterator<Item = S>,
+    ) -> QuoteStreamBuilder<'a, Client>
+    where
+        Client: Request,
+        S: Into<String>,
+    {
+        let subscriptions = Subscriptions::default();
+        subscriptions.subscribe(symbols);
+        QuoteStreamBuilder {
+            client: client.into(),
+            subscriptions,
+            source: QuoteSource::default(),
+            interval: DEFAULT_INTERVAL,
+            market_hours: Some(MarketHours::default()),
+        }
+    }
+
+    /// Start polling `symbols`.
+    pub fn subscribe<S: Into<String>>(&self, symbols: impl IntoIterator<Item = S>) {
+        self.subscriptions.subscribe(symbols);
+    }
+
+    /// Stop polling `symbols`.
+    pub fn unsubscribe<S: Into<String>>(&self, symbols: impl IntoIterator<Item = S>) {
+        self.subscriptions.unsubscribe(symbols);
+    }
+
+    /// Handle for changing the symbols from elsewhere, e.g. another task.
+    pub fn subscriptions(&self) -> Subscriptions {
+        self.subscriptions.clone()
+    }
+}
+
+impl Stream for QuoteStream<'_> {
+    type Item = Result<Quote>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().inner.as_mut().poll_next(cx)
+    }
+}
+
+/// State behind a [`QuoteStream`]
+struct Poller<'a, Client: Request> {
+    client: ClientRef<'a, Client>,
+    subscriptions: Subscriptions,
+    source: QuoteSource,
+    interval: Duration,
+    market_hours: Option<MarketHours>,
+    /// Last quote yielded per symbol
+    last: HashMap<String, Quote>,
+    /// Changes from the latest poll not yet yielded
+    pending: VecDeque<Result<Quote>>,
+    next_poll: Option<Instant>,
+}
+
+impl<Client: Request> Poller<'_, Client> {
+    async fn next(&mut self) -> Result<Quote> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return item;
+            }
+            self.wait().await;
+            self.poll().await;
+        }
+    }
+
+    /// Sleep until the next tick, and then until the market is open.
+    async fn wait(&mut self) {
+        if let Some(next_poll) = self.next_poll {
+            tokio::time::sleep_until(next_poll).await;
+        }
+        if let Some(hours) = self.market_hours {
+            let now = SystemTime::now();
+            if let Ok(closed_for) = hours.next_open(now).duration_since(now) {
+                tokio::time::sleep(closed_for).await;
+            }
+        }
+        self.next_poll = Some(Instant::now() + self.interval);
+    }
+
+    /// Fetch every subscribed symbol and queue the quotes that changed.
+    async fn poll(&mut self) {
+        let symbols = self.subscriptions.symbols();
+        self.last.retain(|symbol, _| symbols.contains(symbol));
+        if symbols.is_empty() {
+            return;
+        }
+
+        let quotes = match self.source {
+            QuoteSource::GlobalQuote => self
+                .client
+                .fan_out(symbols, rest::quotes::global_quote)
+                .get()
+                .await
+                .into_values()
+                .map(|body| Quote::from_global_quote(&body?))
+                .collect(),
+            QuoteSource::Bulk => {
+                let bulk = rest::quotes::realtime_bulk(&*self.client, symbols)
+                    .chunked()
+                    .get()
+                    .await;
+                match bulk.and_then(|bulk| Quote::from_bulk_quotes(&bulk.quotes)) {
+                    Ok(quotes) => quotes.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                }
+            }
+        };
+
+        for quote in quotes {
+            match quote {
+                Ok(quote) if self.last.get(&quote.symbol) == Some(&quote) => {}
+                Ok(quote) => {
+                    self.last.insert(quote.symbol.clone(), quote.clone());
+                    self.pending.push_back(Ok(quote));
+                }
+                Err(e) => self.pending.push_back(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use std::time::UNIX_EPOCH;
+
+    use crate::client::AlphaVantage;
+    use crate::mock::{Matcher, MockClient, MockResponse};
+
+    /// Mock playing a scripted price sequence per symbol, repeating the last
+    /// price once a sequence runs out
+    fn script(prices: &[(&str, &[f64])]) -> MockClient {
+        let mock = MockClient::default();
+        for (symbol, prices) in prices {
+            let quotes = prices.iter().map(|price| {
+                let body = serde_json::json!({
+                    "Global Quote": { "01. symbol": symbol, "05. price": format!("{price:.4}") }
+                });
+                MockResponse::ok(body.to_string())
+            });
+            mock.on_sequence(Matcher::function("GLOBAL_QUOTE").symbol(*symbol), quotes);
+        }
+        mock.on(Matcher::any(), MockResponse::ok(r#"{"Global Quote": {}}"#));
+        mock
+    }
+
+    async fn next(quotes: &mut QuoteStream<'_>) -> Result<Quote> {
+        tokio::time::timeout(Duration::from_secs(5), quotes.next())
+            .await
+            .expect("stream stalled")
+            .expect("stream ended")
+    }
+
+    #[tokio::test]
+    async fn yields_changes_and_follows_subscriptions() {
+        let prices = script(&[("IBM", &[100.0, 100.0, 101.0]), ("AAPL", &[200.0]), ("MSFT", &[300.0])]);
+        let client = AlphaVantage::default().with_client(prices.clone()).with_key("test");
+        let mut quotes = QuoteStream::builder(&client, ["ibm", "AAPL"])
+            .interval(Duration::from_millis(1))
+            .market_hours(None)
+            .start();
+
+        let seen: Vec<(String, f64)> = [
+            next(&mut quotes).await.unwrap(),
+            next(&mut quotes).await.unwrap(),
+            next(&mut quotes).await.unwrap(),
+        ]
+        .into_iter()
+        .map(|q| (q.symbol, q.price))
+        .collect();
+        assert_eq!(
+            seen,
+            [
+                ("AAPL".to_string(), 200.0),
+                ("IBM".to_string(), 100.0),
+                ("IBM".to_string(), 101.0)
+            ]
+        );
+
+        quotes.unsubscribe(["AAPL"]);
+        quotes.subscriptions().subscribe(["MSFT", "NOPE"]);
+        assert_eq!(next(&mut quotes).await.unwrap().symbol, "MSFT");
+        let failed = next(&mut quotes).await.unwrap_err();
+        assert!(failed.to_string().contains("Global Quote"), "got: {failed}");
+
+        let requests = prices.requests();
+        let last_poll = &requests[requests.len() - 3..];
+        assert!(
+            last_poll.iter().all(|url| !url.contains("symbol=AAPL")),
+            "got: {last_poll:?}"
+        );
+    }
+
+    #[test]
+    fn eastern_time_switches_at_two_am() {
+        let utc = |y, m, d, h, min, sec| {
+            NaiveDate::from_ymd_opt(y, m, d)
+                .and_then(|day| day.and_hms_opt(h, min, sec))
+                .unwrap()
+                .and_utc()
+        };
+        let local = |utc| eastern(utc).format("%Y-%m-%d %H:%M:%S").to_string();
+
+        // Spring forward on 2025-03-09: 1:59:59 EST is followed by 3:00 EDT
+        assert_eq!(local(utc(2025, 3, 9, 5, 0, 0)), "2025-03-09 00:00:00");
+        assert_eq!(local(utc(2025, 3, 9, 6, 59, 59)), "2025-03-09 01:59:59");
+        assert_eq!(local(utc(2025, 3, 9, 7, 0, 0)), "2025-03-09 03:00:00");
+        assert_eq!(local(utc(2025, 3, 9, 8, 0, 0)), "2025-03-09 04:00:00");
+        // Not on the first Sunday of March
+        assert_eq!(local(utc(2025, 3, 2, 7, 0, 0)), "2025-03-02 02:00:00");
+
+        // Fall back on 2025-11-02: 1:59:59 EDT is followed by 1:00 EST
+        assert_eq!(local(utc(2025, 11, 2, 4, 0, 0)), "2025-11-02 00:00:00");
+        assert_eq!(local(utc(2025, 11, 2, 5, 59, 59)), "2025-11-02 01:59:59");
+        assert_eq!(local(utc(2025, 11, 2, 6, 0, 0)), "2025-11-02 01:00:00");
+        assert_eq!(local(utc(2025, 11, 2, 7, 0, 0)), "2025-11-02 02:00:00");
+
+        // The same rule in 2024, when the switches fell on March 10 and November 3
+        assert_eq!(local(utc(2024, 3, 10, 6, 59, 59)), "2024-03-10 01:59:59");
+        assert_eq!(local(utc(2024, 3, 10, 7, 0, 0)), "2024-03-10 03:00:00");
+        assert_eq!(local(utc(2024, 11, 3, 5, 59, 59)), "2024-11-03 01:59:59");
+        assert_eq!(local(utc(2024, 11, 3, 6, 0, 0)), "2024-11-03 01:00:00");
+    }
+
+    #[test]
+    fn market_hours_follow_eastern_time() {
+        let at = |secs: u64| UNIX_EPOCH + Duration::from_secs(secs);
+        let hours = MarketHours::US_EQUITIES;
+
+        assert!(hours.is_open(at(1_721_052_000))); // Mon 2024-07-15 10:00 EDT
+        assert!(!hours.is_open(at(1_705_327_200))); // Mon 2024-01-15 09:00 EST
+        assert!(hours.is_open(at(1_705_329_000))); // Mon 2024-01-15 09:30 EST
+        assert!(!hours.is_open(at(1_721_487_600))); // Sat 2024-07-20 11:00 EDT
+
+        // Friday after the close to Monday's open
+        assert_eq!(hours.next_open(at(1_721_422_800)), at(1_721_655_000));
+        // Across the switch to daylight saving time
+        assert_eq!(hours.next_open(at(1_709_931_600)), at(1_710_163_800));
+        // And back to standard time
+        assert_eq!(hours.next_open(at(1_730_494_800)), at(1_730_730_600));
+        assert!(!hours.is_open(at(1_730_727_000))); // Mon 2024-11-04 08:30 EST
+    }
+}
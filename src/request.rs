@@ -7,10 +7,16 @@ use std::future::Future;
 
 pub mod common;
 pub mod fundamentals;
+pub mod global_quote;
+#[cfg(feature = "hyper")]
+pub mod hyper_client;
 pub mod realtime_bulk_quotes;
 pub mod time_series;
 
-pub use realtime_bulk_quotes::RealtimeBulkQuotes;
+pub use global_quote::GlobalQuote;
+#[cfg(feature = "hyper")]
+pub use hyper_client::{HyperClientBuilder, TlsBackend};
+pub use realtime_bulk_quotes::{ChunkedBulkQuotes, RealtimeBulkQuotes};
 
 /// Trait for HTTP clients that can make requests to the Alpha Vantage API.
 ///
@@ -32,12 +38,23 @@ pub trait Request: Send + Sync {
 }
 
 /// HTTP response implementation
+#[derive(Debug)]
 pub struct HttpResponse {
     status: u16,
     body: String,
     request_id: Option<String>,
 }
 
+impl HttpResponse {
+    pub(crate) fn new(status: u16, body: String, request_id: Option<String>) -> Self {
+        Self {
+            status,
+            body,
+            request_id,
+        }
+    }
+}
+
 impl Response for HttpResponse {
     fn status(&self) -> u16 {
         self.status
@@ -93,106 +110,124 @@ impl Request for reqwest::Client {
 
 #[cfg(feature = "hyper")]
 /// Hyper client wrapper
+///
+/// Use [`HyperClientBuilder`] to configure timeouts, proxies and TLS.
 #[derive(Clone)]
 pub struct HyperClient {
     client: std::sync::Arc<
-        hyper_util::client::legacy::Client<
-            hyper_tls::HttpsConnector<hyper_util::client::legacy::connect::HttpConnector>,
-            http_body_util::Full<hyper::body::Bytes>,
-        >,
+        hyper_util::client::legacy::Client<hyper_client::Connector, http_body_util::Full<hyper::body::Bytes>>,
     >,
+    user_agent: hyper::header::HeaderValue,
+    timeout: Option<std::time::Duration>,
+    proxy: Option<std::sync::Arc<hyper_util::client::proxy::matcher::Matcher>>,
 }
 
 #[cfg(feature = "hyper")]
-impl Request for HyperClient {
-    type Response = HttpResponse;
+impl std::fmt::Debug for HyperClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HyperClient")
+            .field("user_agent", &self.user_agent)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
 
-    fn new() -> Self {
-        let https = hyper_tls::HttpsConnector::new();
-        let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new()).build(https);
-        Self {
-            client: std::sync::Arc::new(client),
-        }
+#[cfg(feature = "hyper")]
+impl HyperClient {
+    /// Start configuring a client.
+    pub fn builder() -> HyperClientBuilder {
+        HyperClientBuilder::new()
     }
 
-    async fn get(&self, url: &str) -> Result<Self::Response> {
+    async fn send(&self, request: hyper::Request<http_body_util::Full<hyper::body::Bytes>>) -> Result<HttpResponse> {
         use http_body_util::BodyExt;
 
+        let exchange = async {
+            let response = self
+                .client
+                .request(request)
+                .await
+                .map_err(|e| crate::error::Error::Custom(format!("HTTP request failed: {e}")))?;
+
+            let status = response.status().as_u16();
+            let request_id = response
+                .headers()
+                .get("X-Request-Id")
+                .and_then(|h| h.to_str().ok().map(|s| s.to_string()));
+
+            let body_bytes = response
+                .into_body()
+                .collect()
+                .await
+                .map_err(|e| crate::error::Error::Custom(format!("Failed to read response body: {e}")))?
+                .to_bytes();
+
+            let body = String::from_utf8(body_bytes.to_vec())
+                .map_err(|e| crate::error::Error::Custom(format!("Invalid UTF-8 in response: {e}")))?;
+
+            Ok(HttpResponse {
+                status,
+                body,
+                request_id,
+            })
+        };
+
+        match self.timeout {
+            Some(limit) => tokio::time::timeout(limit, exchange)
+                .await
+                .map_err(|_| crate::error::Error::Timeout(limit))?,
+            None => exchange.await,
+        }
+    }
+
+    fn request(&self, method: hyper::Method, url: &str) -> Result<hyper::http::request::Builder> {
         let uri: hyper::Uri = url
             .parse()
             .map_err(|e| crate::error::Error::Custom(format!("Invalid URL: {e}")))?;
+        let proxy_authorization = self
+            .proxy
+            .as_deref()
+            .and_then(|proxy| hyper_client::proxy_authorization(proxy, &uri));
+        let mut builder = hyper::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(hyper::header::USER_AGENT, self.user_agent.clone());
+        if let Some(auth) = proxy_authorization {
+            builder = builder.header(hyper::header::PROXY_AUTHORIZATION, auth);
+        }
+        Ok(builder)
+    }
+}
 
-        let response = self
-            .client
-            .get(uri)
-            .await
-            .map_err(|e| crate::error::Error::Custom(format!("HTTP request failed: {e}")))?;
-
-        let status = response.status().as_u16();
-        let request_id = response
-            .headers()
-            .get("X-Request-Id")
-            .and_then(|h| h.to_str().ok().map(|s| s.to_string()));
+#[cfg(feature = "hyper")]
+impl Request for HyperClient {
+    type Response = HttpResponse;
 
-        let body_bytes = response
-            .into_body()
-            .collect()
-            .await
-            .map_err(|e| crate::error::Error::Custom(format!("Failed to read response body: {e}")))?
-            .to_bytes();
+    /// Create a client with the default [`HyperClientBuilder`] configuration.
+    fn new() -> Self {
+        HyperClientBuilder::new()
+            .build()
+            .expect("default HyperClient configuration is valid")
+    }
 
-        let body = String::from_utf8(body_bytes.to_vec())
-            .map_err(|e| crate::error::Error::Custom(format!("Invalid UTF-8 in response: {e}")))?;
+    async fn get(&self, url: &str) -> Result<Self::Response> {
+        let req = self
+            .request(hyper::Method::GET, url)?
+            .body(http_body_util::Full::default())
+            .map_err(|e| crate::error::Error::Custom(format!("Failed to build request: {e}")))?;
 
-        Ok(HttpResponse {
-            status,
-            body,
-            request_id,
-        })
+        self.send(req).await
     }
 
     async fn post(&self, url: &str, body_str: &str) -> Result<Self::Response> {
-        use http_body_util::BodyExt;
-
-        let uri: hyper::Uri = url
-            .parse()
-            .map_err(|e| crate::error::Error::Custom(format!("Invalid URL: {e}")))?;
-
-        let req = hyper::Request::builder()
-            .method(hyper::Method::POST)
-            .uri(uri)
+        let req = self
+            .request(hyper::Method::POST, url)?
             .header("content-type", "application/json")
             .body(http_body_util::Full::new(hyper::body::Bytes::from(
                 body_str.to_string(),
             )))
             .map_err(|e| crate::error::Error::Custom(format!("Failed to build request: {e}")))?;
 
-        let response = self
-            .client
-            .request(req)
-            .await
-            .map_err(|e| crate::error::Error::Custom(format!("HTTP request failed: {e}")))?;
-
-        let status = response.status().as_u16();
-        let request_id = response
-            .headers()
-            .get("X-Request-Id")
-            .and_then(|h| h.to_str().ok().map(|s| s.to_string()));
-
-        let body_bytes = response
-            .into_body()
-            .collect()
-            .await
-            .map_err(|e| crate::error::Error::Custom(format!("Failed to read response body: {e}")))?
-            .to_bytes();
-
-        let body = String::from_utf8(body_bytes.to_vec())
-            .map_err(|e| crate::error::Error::Custom(format!("Invalid UTF-8 in response: {e}")))?;
-
-        Ok(HttpResponse {
-            status,
-            body,
-            request_id,
-        })
+        self.send(req).await
     }
 }
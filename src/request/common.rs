@@ -31,7 +31,7 @@ impl From<String> for SortOrder {
 }
 
 /// Output size for Alpha Vantage time series
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum OutputSize {
     /// Compact (latest 100 data points)
@@ -51,8 +51,38 @@ impl FromStr for OutputSize {
     }
 }
 
+/// Reporting period of a financial statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    /// Fiscal years (`annualReports`)
+    Annual,
+    /// Fiscal quarters (`quarterlyReports`)
+    Quarterly,
+}
+
+impl FromStr for Period {
+    type Err = crate::error::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "annual" => Ok(Period::Annual),
+            "quarterly" => Ok(Period::Quarterly),
+            _ => Err(crate::error::Error::Custom(format!("Invalid period: {s}"))),
+        }
+    }
+}
+
+impl std::fmt::Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Period::Annual => write!(f, "annual"),
+            Period::Quarterly => write!(f, "quarterly"),
+        }
+    }
+}
+
 /// Time series interval
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Interval {
     /// 1 minute
@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::client::AlphaVantage;
+use crate::client::ClientRef;
 use crate::error::Result;
 use crate::execute::Execute;
 use crate::processor::{Processor, Raw};
@@ -9,7 +9,7 @@ use crate::request::Request;
 
 /// Earnings request builder
 pub struct Earnings<'a, Client: Request, P: Processor = Raw> {
-    client: &'a AlphaVantage<Client>,
+    client: ClientRef<'a, Client>,
     /// Stock symbol
     pub symbol: String,
     processor: P,
@@ -18,9 +18,9 @@ pub struct Earnings<'a, Client: Request, P: Processor = Raw> {
 // Constructor - always starts with Raw
 impl<'a, C: Request> Earnings<'a, C, Raw> {
     /// Create new earnings request (returns raw JSON by default)
-    pub fn new(client: &'a AlphaVantage<C>, symbol: impl Into<String>) -> Self {
+    pub fn new(client: impl Into<ClientRef<'a, C>>, symbol: impl Into<String>) -> Self {
         Self {
-            client,
+            client: client.into(),
             symbol: symbol.into(),
             processor: Raw,
         }
@@ -30,9 +30,55 @@ impl<'a, C: Request> Earnings<'a, C, Raw> {
 // Processor conversion and builder methods work on any processor type
 impl<'a, C: Request, P: Processor + 'a> Earnings<'a, C, P> {
     /// Execute the request and return the result
-    pub fn get(self) -> impl std::future::Future<Output = Result<P::Output>> + 'a {
+    pub fn get(self) -> impl std::future::Future<Output = Result<P::Output>> + Send + 'a {
         Execute::get(self)
     }
+
+    /// Use a different processor for the response
+    pub fn with_processor<P2: Processor>(self, processor: P2) -> Earnings<'a, C, P2> {
+        Earnings {
+            client: self.client,
+            symbol: self.symbol,
+            processor,
+        }
+    }
+
+    /// Convert to DataFrame output (Polars DataFrame)
+    #[cfg(feature = "table")]
+    pub fn as_dataframe(self) -> Earnings<'a, C, crate::processor::TypedTable> {
+        self.with_processor(crate::processor::TypedTable::with_transform(
+            crate::tool_use::earnings_table,
+        ))
+    }
+
+    /// Convert to LazyFrame output (Polars LazyFrame of the `as_dataframe()` table)
+    #[cfg(feature = "lazy")]
+    pub fn as_lazyframe(self) -> Earnings<'a, C, crate::processor::Lazy> {
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::processor::Lazy::from(processor))
+    }
+
+    /// Convert to the `as_dataframe()` table with its [`Provenance`](crate::export::Provenance)
+    #[cfg(feature = "export")]
+    pub fn as_dataset(self) -> Earnings<'a, C, crate::export::Export> {
+        let symbol = self.symbol.clone();
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::export::Export::new(processor, "EARNINGS", Some(symbol)))
+    }
+
+    /// Convert to typed output ([`Earnings`](crate::model::Earnings))
+    #[cfg(feature = "decoder")]
+    pub fn as_decoded(self) -> Earnings<'a, C, crate::processor::Decoder<crate::model::Earnings>> {
+        self.with_processor(crate::processor::Decoder::parse(crate::model::Earnings::from_json))
+    }
+
+    /// Convert to output deserialized with serde into `T`
+    #[cfg(feature = "decoder")]
+    pub fn as_json<T: serde::de::DeserializeOwned>(self) -> Earnings<'a, C, crate::processor::Json<T>> {
+        self.with_processor(crate::processor::Json::new())
+    }
 }
 
 impl<'a, C: Request, P: Processor + 'a> Execute for Earnings<'a, C, P> {
@@ -40,22 +86,11 @@ impl<'a, C: Request, P: Processor + 'a> Execute for Earnings<'a, C, P> {
 
     #[allow(refining_impl_trait_reachable)]
     async fn get(self) -> Result<P::Output> {
-        // Build URL
-        let api_key = self
-            .client
-            .api_key()
-            .ok_or_else(|| crate::error::Error::Custom("API key not set".to_string()))?;
-
-        let params = [
-            "function=EARNINGS".to_string(),
-            format!("symbol={}", self.symbol),
-            format!("apikey={api_key}"),
-        ];
-
-        let url = format!("https://www.alphavantage.co/query?{}", params.join("&"));
+        // Build query parameters
+        let params = ["function=EARNINGS".to_string(), format!("symbol={}", self.symbol)];
 
         // Make request using Request trait
-        let response = self.client.client().get(&url).await;
+        let response = self.client.query(&params).await;
 
         // Process using associated Processor type
         self.processor.process(response)
@@ -0,0 +1,129 @@
+//! Global quote request builder.
+//!
+//! Wraps Alpha Vantage's `GLOBAL_QUOTE` endpoint, which returns the latest
+//! price and volume of a single symbol.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::client::ClientRef;
+use crate::error::Result;
+use crate::execute::Execute;
+use crate::processor::{Processor, Raw};
+use crate::request::Request;
+
+/// Global quote request builder
+pub struct GlobalQuote<'a, Client: Request, P: Processor = Raw> {
+    client: ClientRef<'a, Client>,
+    /// Stock symbol
+    pub symbol: String,
+    /// Data type (json or csv)
+    pub datatype: Option<String>,
+    processor: P,
+}
+
+// Constructor - always starts with Raw
+impl<'a, C: Request> GlobalQuote<'a, C, Raw> {
+    /// Create new global quote request (returns raw JSON by default)
+    pub fn new(client: impl Into<ClientRef<'a, C>>, symbol: impl Into<String>) -> Self {
+        Self {
+            client: client.into(),
+            symbol: symbol.into(),
+            datatype: None,
+            processor: Raw,
+        }
+    }
+}
+
+// Processor conversion and builder methods work on any processor type
+impl<'a, C: Request, P: Processor + 'a> GlobalQuote<'a, C, P> {
+    /// Execute the request and return the result
+    pub fn get(self) -> impl std::future::Future<Output = Result<P::Output>> + Send + 'a {
+        Execute::get(self)
+    }
+
+    /// Set datatype (json or csv)
+    pub fn datatype(mut self, datatype: impl Into<String>) -> Self {
+        self.datatype = Some(datatype.into());
+        self
+    }
+
+    /// Use a different processor for the response
+    pub fn with_processor<P2: Processor>(self, processor: P2) -> GlobalQuote<'a, C, P2> {
+        GlobalQuote {
+            client: self.client,
+            symbol: self.symbol,
+            datatype: self.datatype,
+            processor,
+        }
+    }
+
+    /// Convert to DataFrame output (Polars DataFrame)
+    #[cfg(feature = "table")]
+    pub fn as_dataframe(self) -> GlobalQuote<'a, C, crate::processor::TypedTable> {
+        self.with_processor(crate::processor::TypedTable::with_transform(
+            crate::processor::table::global_quote_table,
+        ))
+    }
+
+    /// Convert to LazyFrame output (Polars LazyFrame of the `as_dataframe()` table)
+    #[cfg(feature = "lazy")]
+    pub fn as_lazyframe(self) -> GlobalQuote<'a, C, crate::processor::Lazy> {
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::processor::Lazy::from(processor))
+    }
+
+    /// Convert to the `as_dataframe()` table with its [`Provenance`](crate::export::Provenance)
+    #[cfg(feature = "export")]
+    pub fn as_dataset(self) -> GlobalQuote<'a, C, crate::export::Export> {
+        let symbol = self.symbol.clone();
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::export::Export::new(processor, "GLOBAL_QUOTE", Some(symbol)))
+    }
+
+    /// Convert to typed output ([`Quote`](crate::quote_stream::Quote))
+    #[cfg(feature = "decoder")]
+    pub fn as_decoded(self) -> GlobalQuote<'a, C, crate::processor::Decoder<crate::quote_stream::Quote>> {
+        self.with_processor(crate::processor::Decoder::parse(
+            crate::quote_stream::Quote::from_global_quote,
+        ))
+    }
+
+    /// Convert to output deserialized with serde into `T`
+    #[cfg(feature = "decoder")]
+    pub fn as_json<T: serde::de::DeserializeOwned>(self) -> GlobalQuote<'a, C, crate::processor::Json<T>> {
+        self.with_processor(crate::processor::Json::new())
+    }
+}
+
+impl<'a, C: Request, P: Processor + 'a> Execute for GlobalQuote<'a, C, P> {
+    type Output = P::Output;
+
+    #[allow(refining_impl_trait_reachable)]
+    async fn get(self) -> Result<P::Output> {
+        // Build query parameters
+        let mut params = vec!["function=GLOBAL_QUOTE".to_string(), format!("symbol={}", self.symbol)];
+
+        if let Some(datatype) = self.datatype {
+            params.push(format!("datatype={datatype}"));
+        }
+
+        // Make request using Request trait
+        let response = self.client.query(&params).await;
+
+        // Process using associated Processor type
+        self.processor.process(response)
+    }
+}
+
+/// JSON-serializable parameters (no client reference)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Params {
+    /// Stock symbol
+    pub symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Data type (json or csv)
+    pub datatype: Option<String>,
+}
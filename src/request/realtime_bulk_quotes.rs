@@ -5,16 +5,24 @@
 //! quotes. This endpoint requires a premium Alpha Vantage subscription.
 //!
 //! Note: this endpoint only returns realtime snapshots. Historical bars still
-//! have to be fetched one symbol at a time via the `TIME_SERIES_*` endpoints.
+//! have to be fetched one symbol at a time via the `TIME_SERIES_*` endpoints;
+//! [`AlphaVantage::fan_out`](crate::client::AlphaVantage::fan_out) runs those
+//! requests concurrently.
+
+use std::collections::HashSet;
+use std::time::Duration;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::client::AlphaVantage;
+use crate::client::ClientRef;
 use crate::error::{Error, Result};
 use crate::execute::Execute;
+use crate::fan_out::{DEFAULT_RATE_LIMIT_WAIT, wait_for_rate_limit};
 use crate::processor::{Processor, Raw};
 use crate::request::Request;
+use crate::response::Response;
+use crate::tool_use::{missing_data, upstream_message};
 
 /// Maximum number of symbols accepted by `REALTIME_BULK_QUOTES` in a single call.
 pub const MAX_BULK_SYMBOLS: usize = 100;
@@ -24,7 +32,7 @@ pub const MAX_BULK_SYMBOLS: usize = 100;
 /// Accepts a list of symbols (up to [`MAX_BULK_SYMBOLS`]) which are sent to
 /// Alpha Vantage as a comma-separated `symbol=` parameter.
 pub struct RealtimeBulkQuotes<'a, Client: Request, P: Processor = Raw> {
-    client: &'a AlphaVantage<Client>,
+    client: ClientRef<'a, Client>,
     /// Stock symbols to fetch quotes for
     pub symbols: Vec<String>,
     /// Data type (json or csv)
@@ -36,13 +44,13 @@ impl<'a, C: Request> RealtimeBulkQuotes<'a, C, Raw> {
     /// Create a new realtime bulk quotes request (returns raw JSON by default).
     ///
     /// Accepts any iterable of string-like values.
-    pub fn new<I, S>(client: &'a AlphaVantage<C>, symbols: I) -> Self
+    pub fn new<I, S>(client: impl Into<ClientRef<'a, C>>, symbols: I) -> Self
     where
         I: IntoIterator<Item = S>,
         S: Into<String>,
     {
         Self {
-            client,
+            client: client.into(),
             symbols: symbols.into_iter().map(Into::into).collect(),
             datatype: None,
             processor: Raw,
@@ -52,7 +60,7 @@ impl<'a, C: Request> RealtimeBulkQuotes<'a, C, Raw> {
 
 impl<'a, C: Request, P: Processor + 'a> RealtimeBulkQuotes<'a, C, P> {
     /// Execute the request and return the result
-    pub fn get(self) -> impl std::future::Future<Output = Result<P::Output>> + 'a {
+    pub fn get(self) -> impl std::future::Future<Output = Result<P::Output>> + Send + 'a {
         Execute::get(self)
     }
 
@@ -61,6 +69,73 @@ impl<'a, C: Request, P: Processor + 'a> RealtimeBulkQuotes<'a, C, P> {
         self.datatype = Some(datatype.into());
         self
     }
+
+    /// Accept any number of symbols, requesting them in batches of
+    /// [`MAX_BULK_SYMBOLS`]. See [`ChunkedBulkQuotes`].
+    pub fn chunked(self) -> ChunkedBulkQuotes<'a, C, P> {
+        let mut seen = HashSet::new();
+        let symbols = self
+            .symbols
+            .into_iter()
+            .filter(|symbol| seen.insert(symbol.to_ascii_uppercase()))
+            .collect();
+
+        ChunkedBulkQuotes {
+            client: self.client,
+            symbols,
+            datatype: self.datatype,
+            rate_limit_wait: DEFAULT_RATE_LIMIT_WAIT,
+            processor: self.processor,
+        }
+    }
+
+    /// Use a different processor for the response
+    pub fn with_processor<P2: Processor>(self, processor: P2) -> RealtimeBulkQuotes<'a, C, P2> {
+        RealtimeBulkQuotes {
+            client: self.client,
+            symbols: self.symbols,
+            datatype: self.datatype,
+            processor,
+        }
+    }
+
+    /// Convert to DataFrame output (Polars DataFrame)
+    #[cfg(feature = "table")]
+    pub fn as_dataframe(self) -> RealtimeBulkQuotes<'a, C, crate::processor::TypedTable> {
+        self.with_processor(crate::processor::TypedTable::with_transform(
+            crate::processor::table::bulk_quotes_table,
+        ))
+    }
+
+    /// Convert to LazyFrame output (Polars LazyFrame of the `as_dataframe()` table)
+    #[cfg(feature = "lazy")]
+    pub fn as_lazyframe(self) -> RealtimeBulkQuotes<'a, C, crate::processor::Lazy> {
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::processor::Lazy::from(processor))
+    }
+
+    /// Convert to the `as_dataframe()` table with its [`Provenance`](crate::export::Provenance)
+    #[cfg(feature = "export")]
+    pub fn as_dataset(self) -> RealtimeBulkQuotes<'a, C, crate::export::Export> {
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::export::Export::new(processor, "REALTIME_BULK_QUOTES", None))
+    }
+
+    /// Convert to typed output ([`Quote`](crate::quote_stream::Quote)s)
+    #[cfg(feature = "decoder")]
+    pub fn as_decoded(self) -> RealtimeBulkQuotes<'a, C, crate::processor::Decoder<Vec<crate::quote_stream::Quote>>> {
+        self.with_processor(crate::processor::Decoder::parse(
+            crate::quote_stream::Quote::from_bulk_quotes,
+        ))
+    }
+
+    /// Convert to output deserialized with serde into `T`
+    #[cfg(feature = "decoder")]
+    pub fn as_json<T: serde::de::DeserializeOwned>(self) -> RealtimeBulkQuotes<'a, C, crate::processor::Json<T>> {
+        self.with_processor(crate::processor::Json::new())
+    }
 }
 
 impl<'a, C: Request, P: Processor + 'a> Execute for RealtimeBulkQuotes<'a, C, P> {
@@ -75,35 +150,212 @@ impl<'a, C: Request, P: Processor + 'a> Execute for RealtimeBulkQuotes<'a, C, P>
         }
         if self.symbols.len() > MAX_BULK_SYMBOLS {
             return Err(Error::Custom(format!(
-                "REALTIME_BULK_QUOTES accepts at most {MAX_BULK_SYMBOLS} symbols, got {}",
+                "REALTIME_BULK_QUOTES accepts at most {MAX_BULK_SYMBOLS} symbols, got {}; use `chunked()` for more",
                 self.symbols.len()
             )));
         }
 
-        let api_key = self
-            .client
-            .api_key()
-            .ok_or_else(|| Error::Custom("API key not set".to_string()))?;
-
         let symbols = self.symbols.join(",");
-        let mut params = vec![
-            "function=REALTIME_BULK_QUOTES".to_string(),
-            format!("symbol={symbols}"),
-            format!("apikey={api_key}"),
-        ];
+        let mut params = vec!["function=REALTIME_BULK_QUOTES".to_string(), format!("symbol={symbols}")];
 
         if let Some(datatype) = self.datatype {
             params.push(format!("datatype={datatype}"));
         }
 
-        let url = format!("https://www.alphavantage.co/query?{}", params.join("&"));
-
-        let response = self.client.client().get(&url).await;
+        let response = self.client.query(&params).await;
 
         self.processor.process(response)
     }
 }
 
+/// Realtime bulk quotes for any number of symbols, created by [`RealtimeBulkQuotes::chunked`]
+///
+/// Symbols are deduplicated, ignoring case, and requested [`MAX_BULK_SYMBOLS`] at a
+/// time, one batch after another. A batch refused by a key pool at its quota waits
+/// for a key to free up, as in [`fan_out`](crate::fan_out). The batches' outputs
+/// are merged into one [`BulkQuotes`], which also lists the symbols Alpha Vantage
+/// did not return. If any batch fails, including one answered with a rate-limit
+/// `Note`, an `Information` notice or an `Error Message` instead of quotes, the
+/// whole request fails.
+pub struct ChunkedBulkQuotes<'a, Client: Request, P: Processor = Raw> {
+    client: ClientRef<'a, Client>,
+    /// Stock symbols to fetch quotes for, without duplicates
+    pub symbols: Vec<String>,
+    /// Data type (json or csv)
+    pub datatype: Option<String>,
+    rate_limit_wait: Duration,
+    processor: P,
+}
+
+impl<'a, C: Request, P: Processor + 'a> ChunkedBulkQuotes<'a, C, P>
+where
+    P::Output: MergeQuotes,
+{
+    /// Execute the request and return the result
+    pub fn get(self) -> impl std::future::Future<Output = Result<BulkQuotes<P::Output>>> + Send + 'a {
+        Execute::get(self)
+    }
+
+    /// Set the longest a batch waits in total for the key pool to free up before
+    /// failing with [`Error::RateLimited`].
+    pub fn rate_limit_wait(mut self, limit: Duration) -> Self {
+        self.rate_limit_wait = limit;
+        self
+    }
+}
+
+impl<'a, C: Request, P: Processor + 'a> Execute for ChunkedBulkQuotes<'a, C, P>
+where
+    P::Output: MergeQuotes,
+{
+    type Output = BulkQuotes<P::Output>;
+
+    #[allow(refining_impl_trait_reachable)]
+    async fn get(self) -> Result<BulkQuotes<P::Output>> {
+        if self.symbols.is_empty() {
+            return Err(Error::Custom(
+                "REALTIME_BULK_QUOTES requires at least one symbol".to_string(),
+            ));
+        }
+
+        let mut parts = Vec::new();
+        let mut returned = HashSet::new();
+        for batch in self.symbols.chunks(MAX_BULK_SYMBOLS) {
+            let mut params = vec![
+                "function=REALTIME_BULK_QUOTES".to_string(),
+                format!("symbol={}", batch.join(",")),
+            ];
+            if let Some(datatype) = &self.datatype {
+                params.push(format!("datatype={datatype}"));
+            }
+
+            let response = wait_for_rate_limit(self.rate_limit_wait, || self.client.query(&params)).await;
+            if let Ok(response) = &response {
+                returned.extend(returned_symbols(response.body())?);
+            }
+            parts.push(self.processor.process(response)?);
+        }
+
+        let missing = self
+            .symbols
+            .into_iter()
+            .filter(|symbol| !returned.contains(&symbol.to_ascii_uppercase()))
+            .collect();
+        Ok(BulkQuotes {
+            quotes: MergeQuotes::merge(parts)?,
+            missing,
+        })
+    }
+}
+
+/// Merged output of a [`ChunkedBulkQuotes`] request
+#[derive(Debug, Clone)]
+pub struct BulkQuotes<T> {
+    /// Quotes of every batch, merged
+    pub quotes: T,
+    /// Requested symbols Alpha Vantage returned no quote for
+    pub missing: Vec<String>,
+}
+
+/// Processor outputs that can be merged across [`ChunkedBulkQuotes`] batches
+pub trait MergeQuotes: Sized + Send {
+    /// Merge the outputs of consecutive batches, in order.
+    fn merge(parts: Vec<Self>) -> Result<Self>;
+}
+
+impl MergeQuotes for String {
+    /// Concatenates the `data` arrays of JSON bodies, or the rows of CSV bodies
+    /// under the first header. A single body is returned unchanged.
+    fn merge(parts: Vec<Self>) -> Result<Self> {
+        let mut parts = parts.into_iter();
+        let Some(first) = parts.next() else {
+            return Ok(String::new());
+        };
+        if parts.len() == 0 {
+            return Ok(first);
+        }
+
+        if !first.trim_start().starts_with('{') {
+            let mut merged = first;
+            for part in parts {
+                let rows = part.split_once('\n').map_or("", |(_, rows)| rows);
+                if rows.is_empty() {
+                    continue;
+                }
+                if !merged.ends_with('\n') {
+                    merged.push('\n');
+                }
+                merged.push_str(rows);
+            }
+            return Ok(merged);
+        }
+
+        let mut merged: serde_json::Value = serde_json::from_str(&first)?;
+        let mut data = quote_rows(&mut merged);
+        for part in parts {
+            data.extend(quote_rows(&mut serde_json::from_str(&part)?));
+        }
+        merged["data"] = serde_json::Value::Array(data);
+        Ok(merged.to_string())
+    }
+}
+
+#[cfg(feature = "table")]
+impl MergeQuotes for polars_core::frame::DataFrame {
+    /// Stacks the frames' rows; empty frames are skipped.
+    fn merge(parts: Vec<Self>) -> Result<Self> {
+        let mut parts = parts.into_iter().filter(|df| df.height() > 0);
+        let Some(mut merged) = parts.next() else {
+            return Ok(Self::empty());
+        };
+        for part in parts {
+            merged
+                .vstack_mut(&part)
+                .map_err(|e| Error::Custom(format!("Failed to merge quote batches: {e}")))?;
+        }
+        Ok(merged)
+    }
+}
+
+/// Take the `data` array out of a bulk quotes body.
+fn quote_rows(body: &mut serde_json::Value) -> Vec<serde_json::Value> {
+    match body.get_mut("data").map(serde_json::Value::take) {
+        Some(serde_json::Value::Array(rows)) => rows,
+        _ => Vec::new(),
+    }
+}
+
+/// Upper-cased symbols quoted in a JSON or CSV bulk quotes body.
+///
+/// Fails with Alpha Vantage's message if it sent one in place of the quotes.
+fn returned_symbols(body: &str) -> Result<Vec<String>> {
+    let uppercase = |symbol: &str| symbol.trim().to_ascii_uppercase();
+    if body.trim_start().starts_with('{') {
+        let Ok(mut body) = serde_json::from_str::<serde_json::Value>(body) else {
+            return Ok(Vec::new());
+        };
+        if body.get("data").is_none() && upstream_message(&body).is_some() {
+            return Err(missing_data(&body, "data"));
+        }
+        return Ok(quote_rows(&mut body)
+            .iter()
+            .filter_map(|quote| quote.get("symbol")?.as_str().map(uppercase))
+            .collect());
+    }
+
+    let mut lines = body.lines();
+    let Some(column) = lines
+        .next()
+        .and_then(|header| header.split(',').position(|c| c.trim() == "symbol"))
+    else {
+        return Ok(Vec::new());
+    };
+    Ok(lines
+        .filter_map(|line| line.split(',').nth(column))
+        .map(uppercase)
+        .collect())
+}
+
 /// JSON-serializable parameters (no client reference)
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Params {
@@ -113,3 +365,99 @@ pub struct Params {
     /// Data type (json or csv)
     pub datatype: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::AlphaVantage;
+    use crate::mock::{Matcher, MockClient, MockResponse, RATE_LIMIT_NOTE};
+
+    #[test]
+    fn merges_json_and_csv_batches() {
+        let merged = String::merge(vec![
+            r#"{"endpoint": "Realtime Bulk Quotes", "data": [{"symbol": "IBM"}]}"#.to_string(),
+            r#"{"endpoint": "Realtime Bulk Quotes", "data": [{"symbol": "aon"}, {"symbol": "SPY"}]}"#.to_string(),
+        ])
+        .unwrap();
+        assert_eq!(returned_symbols(&merged).unwrap(), ["IBM", "AON", "SPY"]);
+
+        let merged = String::merge(vec![
+            "symbol,close\nIBM,1\n".to_string(),
+            "symbol,close\nSPY,2".to_string(),
+            "symbol,close\n".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(merged, "symbol,close\nIBM,1\nSPY,2");
+        assert_eq!(returned_symbols(&merged).unwrap(), ["IBM", "SPY"]);
+
+        let single = r#"{ "data": [] }"#.to_string();
+        assert_eq!(String::merge(vec![single.clone()]).unwrap(), single);
+    }
+
+    /// A bulk quotes body quoting `symbols`
+    fn quotes_body(symbols: &[String]) -> String {
+        let data: Vec<serde_json::Value> = symbols
+            .iter()
+            .map(|symbol| serde_json::json!({ "symbol": symbol, "close": "100.0000" }))
+            .collect();
+        serde_json::json!({ "endpoint": "Realtime Bulk Quotes", "data": data }).to_string()
+    }
+
+    #[tokio::test]
+    async fn chunks_deduplicated_symbols_and_reports_missing_ones() {
+        let unique: Vec<String> = (0..150).map(|i| format!("S{i:03}")).collect();
+        // Case-only duplicates are dropped, keeping the first spelling
+        let mut symbols = unique.clone();
+        symbols.insert(10, "s005".to_string());
+        symbols.push("s120".to_string());
+
+        let mut second: Vec<String> = unique[100..].to_vec();
+        second.retain(|symbol| symbol != "S137");
+        let mock = MockClient::default();
+        mock.on_sequence(
+            Matcher::function("REALTIME_BULK_QUOTES"),
+            [
+                MockResponse::ok(quotes_body(&unique[..100])),
+                MockResponse::ok(quotes_body(&second)),
+            ],
+        );
+        let client = AlphaVantage::default().with_client(mock.clone()).with_key("test");
+
+        let result = RealtimeBulkQuotes::new(&client, symbols).chunked().get().await.unwrap();
+
+        let batches: Vec<String> = mock
+            .requests()
+            .iter()
+            .map(|url| crate::cache::query_param(url, "symbol").unwrap())
+            .collect();
+        assert_eq!(batches, [unique[..100].join(","), unique[100..].join(",")]);
+
+        let mut expected = unique.clone();
+        expected.retain(|symbol| symbol != "S137");
+        assert_eq!(returned_symbols(&result.quotes).unwrap(), expected);
+        assert_eq!(result.missing, ["S137"]);
+    }
+
+    #[tokio::test]
+    async fn rate_limited_batch_fails_the_request() {
+        let mock = MockClient::default();
+        mock.on_sequence(
+            Matcher::function("REALTIME_BULK_QUOTES"),
+            [
+                MockResponse::ok(r#"{"data": [{"symbol": "S000"}]}"#),
+                MockResponse::rate_limit_note(),
+                MockResponse::ok(r#"{"data": [{"symbol": "S200"}]}"#),
+            ],
+        );
+        let client = AlphaVantage::default().with_client(mock.clone()).with_key("test");
+        let symbols: Vec<String> = (0..250).map(|i| format!("S{i:03}")).collect();
+
+        let err = RealtimeBulkQuotes::new(&client, symbols)
+            .chunked()
+            .get()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(RATE_LIMIT_NOTE), "got: {err}");
+        assert_eq!(mock.requests().len(), 2);
+    }
+}
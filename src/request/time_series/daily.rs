@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::client::AlphaVantage;
+use crate::client::ClientRef;
 use crate::error::Result;
 use crate::execute::Execute;
 use crate::processor::{Processor, Raw};
@@ -10,7 +10,7 @@ use crate::request::common::OutputSize;
 
 /// Time series daily request builder
 pub struct TimeSeriesDaily<'a, Client: Request, P: Processor = Raw> {
-    client: &'a AlphaVantage<Client>,
+    client: ClientRef<'a, Client>,
     /// Stock symbol
     pub symbol: String,
     /// Output size (compact or full)
@@ -23,9 +23,9 @@ pub struct TimeSeriesDaily<'a, Client: Request, P: Processor = Raw> {
 // Constructor - always starts with Raw
 impl<'a, C: Request> TimeSeriesDaily<'a, C, Raw> {
     /// Create new time series daily request (returns raw JSON by default)
-    pub fn new(client: &'a AlphaVantage<C>, symbol: impl Into<String>) -> Self {
+    pub fn new(client: impl Into<ClientRef<'a, C>>, symbol: impl Into<String>) -> Self {
         Self {
-            client,
+            client: client.into(),
             symbol: symbol.into(),
             outputsize: None,
             datatype: None,
@@ -37,7 +37,7 @@ impl<'a, C: Request> TimeSeriesDaily<'a, C, Raw> {
 // Processor conversion and builder methods work on any processor type
 impl<'a, C: Request, P: Processor + 'a> TimeSeriesDaily<'a, C, P> {
     /// Execute the request and return the result
-    pub fn get(self) -> impl std::future::Future<Output = Result<P::Output>> + 'a {
+    pub fn get(self) -> impl std::future::Future<Output = Result<P::Output>> + Send + 'a {
         Execute::get(self)
     }
 
@@ -52,6 +52,54 @@ impl<'a, C: Request, P: Processor + 'a> TimeSeriesDaily<'a, C, P> {
         self.datatype = Some(datatype.into());
         self
     }
+
+    /// Use a different processor for the response
+    pub fn with_processor<P2: Processor>(self, processor: P2) -> TimeSeriesDaily<'a, C, P2> {
+        TimeSeriesDaily {
+            client: self.client,
+            symbol: self.symbol,
+            outputsize: self.outputsize,
+            datatype: self.datatype,
+            processor,
+        }
+    }
+
+    /// Convert to DataFrame output (Polars DataFrame)
+    #[cfg(feature = "table")]
+    pub fn as_dataframe(self) -> TimeSeriesDaily<'a, C, crate::processor::TypedTable> {
+        self.with_processor(crate::processor::TypedTable::with_transform(
+            crate::tool_use::daily_table,
+        ))
+    }
+
+    /// Convert to LazyFrame output (Polars LazyFrame of the `as_dataframe()` table)
+    #[cfg(feature = "lazy")]
+    pub fn as_lazyframe(self) -> TimeSeriesDaily<'a, C, crate::processor::Lazy> {
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::processor::Lazy::from(processor))
+    }
+
+    /// Convert to the `as_dataframe()` table with its [`Provenance`](crate::export::Provenance)
+    #[cfg(feature = "export")]
+    pub fn as_dataset(self) -> TimeSeriesDaily<'a, C, crate::export::Export> {
+        let symbol = self.symbol.clone();
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::export::Export::new(processor, "TIME_SERIES_DAILY", Some(symbol)))
+    }
+
+    /// Convert to typed output ([`TimeSeries`](crate::model::TimeSeries))
+    #[cfg(feature = "decoder")]
+    pub fn as_decoded(self) -> TimeSeriesDaily<'a, C, crate::processor::Decoder<crate::model::TimeSeries>> {
+        self.with_processor(crate::processor::Decoder::parse(crate::model::TimeSeries::from_json))
+    }
+
+    /// Convert to output deserialized with serde into `T`
+    #[cfg(feature = "decoder")]
+    pub fn as_json<T: serde::de::DeserializeOwned>(self) -> TimeSeriesDaily<'a, C, crate::processor::Json<T>> {
+        self.with_processor(crate::processor::Json::new())
+    }
 }
 
 impl<'a, C: Request, P: Processor + 'a> Execute for TimeSeriesDaily<'a, C, P> {
@@ -59,17 +107,8 @@ impl<'a, C: Request, P: Processor + 'a> Execute for TimeSeriesDaily<'a, C, P> {
 
     #[allow(refining_impl_trait_reachable)]
     async fn get(self) -> Result<P::Output> {
-        // Build URL
-        let api_key = self
-            .client
-            .api_key()
-            .ok_or_else(|| crate::error::Error::Custom("API key not set".to_string()))?;
-
-        let mut params = vec![
-            format!("function=TIME_SERIES_DAILY"),
-            format!("symbol={}", self.symbol),
-            format!("apikey={}", api_key),
-        ];
+        // Build query parameters
+        let mut params = vec![format!("function=TIME_SERIES_DAILY"), format!("symbol={}", self.symbol)];
 
         if let Some(size) = self.outputsize {
             params.push(format!("outputsize={size:?}").to_lowercase());
@@ -78,10 +117,8 @@ impl<'a, C: Request, P: Processor + 'a> Execute for TimeSeriesDaily<'a, C, P> {
             params.push(format!("datatype={datatype}"));
         }
 
-        let url = format!("https://www.alphavantage.co/query?{}", params.join("&"));
-
         // Make request using Request trait
-        let response = self.client.client().get(&url).await;
+        let response = self.client.query(&params).await;
 
         // Process using associated Processor type
         self.processor.process(response)
@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::client::AlphaVantage;
+use crate::client::ClientRef;
 use crate::error::Result;
 use crate::execute::Execute;
 use crate::processor::{Processor, Raw};
@@ -10,7 +10,7 @@ use crate::request::common::{Interval, OutputSize};
 
 /// Time series intraday request builder
 pub struct TimeSeriesIntraday<'a, Client: Request, P: Processor = Raw> {
-    client: &'a AlphaVantage<Client>,
+    client: ClientRef<'a, Client>,
     /// Stock symbol
     pub symbol: String,
     /// Time interval
@@ -25,9 +25,9 @@ pub struct TimeSeriesIntraday<'a, Client: Request, P: Processor = Raw> {
 // Constructor - always starts with Raw
 impl<'a, C: Request> TimeSeriesIntraday<'a, C, Raw> {
     /// Create new time series intraday request (returns raw JSON by default)
-    pub fn new(client: &'a AlphaVantage<C>, symbol: impl Into<String>, interval: Interval) -> Self {
+    pub fn new(client: impl Into<ClientRef<'a, C>>, symbol: impl Into<String>, interval: Interval) -> Self {
         Self {
-            client,
+            client: client.into(),
             symbol: symbol.into(),
             interval,
             outputsize: None,
@@ -40,7 +40,7 @@ impl<'a, C: Request> TimeSeriesIntraday<'a, C, Raw> {
 // Processor conversion and builder methods work on any processor type
 impl<'a, C: Request, P: Processor + 'a> TimeSeriesIntraday<'a, C, P> {
     /// Execute the request and return the result
-    pub fn get(self) -> impl std::future::Future<Output = Result<P::Output>> + 'a {
+    pub fn get(self) -> impl std::future::Future<Output = Result<P::Output>> + Send + 'a {
         Execute::get(self)
     }
 
@@ -55,6 +55,60 @@ impl<'a, C: Request, P: Processor + 'a> TimeSeriesIntraday<'a, C, P> {
         self.datatype = Some(datatype.into());
         self
     }
+
+    /// Use a different processor for the response
+    pub fn with_processor<P2: Processor>(self, processor: P2) -> TimeSeriesIntraday<'a, C, P2> {
+        TimeSeriesIntraday {
+            client: self.client,
+            symbol: self.symbol,
+            interval: self.interval,
+            outputsize: self.outputsize,
+            datatype: self.datatype,
+            processor,
+        }
+    }
+
+    /// Convert to DataFrame output (Polars DataFrame)
+    #[cfg(feature = "table")]
+    pub fn as_dataframe(self) -> TimeSeriesIntraday<'a, C, crate::processor::TypedTable> {
+        let interval = self.interval.to_string();
+        self.with_processor(crate::processor::TypedTable::with_transform(move |response| {
+            crate::tool_use::intraday_table(response, &interval)
+        }))
+    }
+
+    /// Convert to LazyFrame output (Polars LazyFrame of the `as_dataframe()` table)
+    #[cfg(feature = "lazy")]
+    pub fn as_lazyframe(self) -> TimeSeriesIntraday<'a, C, crate::processor::Lazy> {
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::processor::Lazy::from(processor))
+    }
+
+    /// Convert to the `as_dataframe()` table with its [`Provenance`](crate::export::Provenance)
+    #[cfg(feature = "export")]
+    pub fn as_dataset(self) -> TimeSeriesIntraday<'a, C, crate::export::Export> {
+        let symbol = self.symbol.clone();
+        let mut table = self.as_dataframe();
+        let processor = std::mem::take(&mut table.processor);
+        table.with_processor(crate::export::Export::new(
+            processor,
+            "TIME_SERIES_INTRADAY",
+            Some(symbol),
+        ))
+    }
+
+    /// Convert to typed output ([`TimeSeries`](crate::model::TimeSeries))
+    #[cfg(feature = "decoder")]
+    pub fn as_decoded(self) -> TimeSeriesIntraday<'a, C, crate::processor::Decoder<crate::model::TimeSeries>> {
+        self.with_processor(crate::processor::Decoder::parse(crate::model::TimeSeries::from_json))
+    }
+
+    /// Convert to output deserialized with serde into `T`
+    #[cfg(feature = "decoder")]
+    pub fn as_json<T: serde::de::DeserializeOwned>(self) -> TimeSeriesIntraday<'a, C, crate::processor::Json<T>> {
+        self.with_processor(crate::processor::Json::new())
+    }
 }
 
 impl<'a, C: Request, P: Processor + 'a> Execute for TimeSeriesIntraday<'a, C, P> {
@@ -62,17 +116,11 @@ impl<'a, C: Request, P: Processor + 'a> Execute for TimeSeriesIntraday<'a, C, P>
 
     #[allow(refining_impl_trait_reachable)]
     async fn get(self) -> Result<P::Output> {
-        // Build URL
-        let api_key = self
-            .client
-            .api_key()
-            .ok_or_else(|| crate::error::Error::Custom("API key not set".to_string()))?;
-
+        // Build query parameters
         let mut params = vec![
             format!("function=TIME_SERIES_INTRADAY"),
             format!("symbol={}", self.symbol),
             format!("interval={}", self.interval),
-            format!("apikey={}", api_key),
         ];
 
         if let Some(size) = self.outputsize {
@@ -82,10 +130,8 @@ impl<'a, C: Request, P: Processor + 'a> Execute for TimeSeriesIntraday<'a, C, P>
             params.push(format!("datatype={datatype}"));
         }
 
-        let url = format!("https://www.alphavantage.co/query?{}", params.join("&"));
-
         // Make request using Request trait
-        let response = self.client.client().get(&url).await;
+        let response = self.client.query(&params).await;
 
         // Process using associated Processor type
         self.processor.process(response)
@@ -1,2 +1,4 @@
 //! Decoded REST API endpoints that return typed structs
-// Note: Decoded endpoints will be implemented as needed
+pub mod fundamentals;
+pub mod quotes;
+pub mod time_series;
@@ -0,0 +1,99 @@
+//! Fundamental data endpoint implementations returning typed structs
+
+use crate::client::ClientRef;
+use crate::model::{self, BalanceSheets, CashFlows, IncomeStatements};
+use crate::processor::Decoder;
+use crate::request::Request;
+use crate::request::fundamentals::{
+    BalanceSheet, CashFlow, CompanyOverview, Earnings, EarningsEstimates, IncomeStatement,
+};
+
+/// Get analyst earnings estimates for a stock
+///
+/// Returns a request builder that will return results as [`model::EarningsEstimates`].
+///
+/// # Example
+/// ```no_run
+/// # use alphav::AlphaVantage;
+/// # async fn example() {
+/// # let client = AlphaVantage::default().with_key("api-key");
+/// let estimates = alphav::rest::decoded::fundamentals::earnings_estimates(&client, "AAPL")
+///     .get()
+///     .await
+///     .unwrap();
+/// if let Some(next) = estimates.horizon("next fiscal year") {
+///     println!("{}: {:?}", next.date, next.eps_estimate_average);
+/// }
+/// # }
+/// ```
+pub fn earnings_estimates<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> EarningsEstimates<'a, Client, Decoder<model::EarningsEstimates>> {
+    EarningsEstimates::new(client, symbol).as_decoded()
+}
+
+/// Get earnings data for a stock
+///
+/// Returns a request builder that will return results as [`model::Earnings`].
+pub fn earnings<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> Earnings<'a, Client, Decoder<model::Earnings>> {
+    Earnings::new(client, symbol).as_decoded()
+}
+
+/// Get company overview for a stock
+///
+/// Returns a request builder that will return results as [`model::CompanyOverview`].
+pub fn company_overview<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> CompanyOverview<'a, Client, Decoder<model::CompanyOverview>> {
+    CompanyOverview::new(client, symbol).as_decoded()
+}
+
+/// Get income statements for a stock
+///
+/// Returns a request builder that will return results as [`IncomeStatements`].
+///
+/// # Example
+/// ```no_run
+/// # use alphav::AlphaVantage;
+/// # async fn example() {
+/// # let client = AlphaVantage::default().with_key("api-key");
+/// let statements = alphav::rest::decoded::fundamentals::income_statement(&client, "IBM")
+///     .get()
+///     .await
+///     .unwrap();
+/// for report in &statements.annual_reports {
+///     println!("{}: {:?}", report.fiscal_date_ending, report.net_income);
+/// }
+/// # }
+/// ```
+pub fn income_statement<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> IncomeStatement<'a, Client, Decoder<IncomeStatements>> {
+    IncomeStatement::new(client, symbol).as_decoded()
+}
+
+/// Get balance sheets for a stock
+///
+/// Returns a request builder that will return results as [`BalanceSheets`].
+pub fn balance_sheet<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> BalanceSheet<'a, Client, Decoder<BalanceSheets>> {
+    BalanceSheet::new(client, symbol).as_decoded()
+}
+
+/// Get cash flow statements for a stock
+///
+/// Returns a request builder that will return results as [`CashFlows`].
+pub fn cash_flow<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> CashFlow<'a, Client, Decoder<CashFlows>> {
+    CashFlow::new(client, symbol).as_decoded()
+}
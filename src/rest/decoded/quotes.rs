@@ -0,0 +1,44 @@
+//! Quote endpoint implementations returning [`Quote`]s
+
+use crate::client::ClientRef;
+use crate::processor::Decoder;
+use crate::quote_stream::Quote;
+use crate::request::Request;
+use crate::request::global_quote::GlobalQuote;
+use crate::request::realtime_bulk_quotes::RealtimeBulkQuotes;
+
+/// Get the latest quote for a single symbol
+///
+/// Returns a request builder that will return results as a [`Quote`].
+///
+/// # Example
+/// ```no_run
+/// # use alphav::AlphaVantage;
+/// # async fn example() {
+/// # let client = AlphaVantage::default().with_key("api-key");
+/// let quote = alphav::rest::decoded::quotes::global_quote(&client, "IBM").get().await.unwrap();
+/// println!("{} {}", quote.symbol, quote.price);
+/// # }
+/// ```
+pub fn global_quote<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> GlobalQuote<'a, Client, Decoder<Quote>> {
+    GlobalQuote::new(client, symbol).as_decoded()
+}
+
+/// Get realtime quotes for up to 100 symbols in a single API call.
+///
+/// Returns a request builder that will return results as [`Quote`]s. Requires a
+/// premium Alpha Vantage subscription.
+pub fn realtime_bulk<'a, Client, I, S>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbols: I,
+) -> RealtimeBulkQuotes<'a, Client, Decoder<Vec<Quote>>>
+where
+    Client: Request,
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    RealtimeBulkQuotes::new(client, symbols).as_decoded()
+}
@@ -0,0 +1,65 @@
+//! Time series endpoint implementations returning [`TimeSeries`]
+
+use crate::client::ClientRef;
+use crate::model::TimeSeries;
+use crate::processor::Decoder;
+use crate::request::Request;
+use crate::request::common::Interval;
+use crate::request::time_series::{TimeSeriesDaily, TimeSeriesIntraday, TimeSeriesMonthly, TimeSeriesWeekly};
+
+/// Get intraday time series for a stock
+///
+/// Returns a request builder that will return results as a [`TimeSeries`].
+///
+/// # Example
+/// ```no_run
+/// # use alphav::AlphaVantage;
+/// # use alphav::request::common::Interval;
+/// # async fn example() {
+/// # let client = AlphaVantage::default().with_key("api-key");
+/// let series = alphav::rest::decoded::time_series::intraday(&client, "AAPL", Interval::FiveMin)
+///     .get()
+///     .await
+///     .unwrap();
+/// for bar in &series.bars {
+///     println!("{} {} {}", bar.timestamp, bar.close, bar.volume);
+/// }
+/// # }
+/// ```
+pub fn intraday<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+    interval: Interval,
+) -> TimeSeriesIntraday<'a, Client, Decoder<TimeSeries>> {
+    TimeSeriesIntraday::new(client, symbol, interval).as_decoded()
+}
+
+/// Get daily time series for a stock
+///
+/// Returns a request builder that will return results as a [`TimeSeries`].
+pub fn daily<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> TimeSeriesDaily<'a, Client, Decoder<TimeSeries>> {
+    TimeSeriesDaily::new(client, symbol).as_decoded()
+}
+
+/// Get weekly time series for a stock
+///
+/// Returns a request builder that will return results as a [`TimeSeries`].
+pub fn weekly<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> TimeSeriesWeekly<'a, Client, Decoder<TimeSeries>> {
+    TimeSeriesWeekly::new(client, symbol).as_decoded()
+}
+
+/// Get monthly time series for a stock
+///
+/// Returns a request builder that will return results as a [`TimeSeries`].
+pub fn monthly<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> TimeSeriesMonthly<'a, Client, Decoder<TimeSeries>> {
+    TimeSeriesMonthly::new(client, symbol).as_decoded()
+}
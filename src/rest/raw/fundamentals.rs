@@ -1,6 +1,6 @@
 //! Fundamental data endpoint implementations returning raw JSON strings
 
-use crate::client::AlphaVantage;
+use crate::client::ClientRef;
 use crate::processor::Raw;
 use crate::request::Request;
 use crate::request::fundamentals::{
@@ -25,7 +25,7 @@ use crate::request::fundamentals::{
 /// # }
 /// ```
 pub fn earnings_estimates<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
 ) -> EarningsEstimates<'a, Client, Raw> {
     EarningsEstimates::new(client, symbol)
@@ -33,7 +33,7 @@ pub fn earnings_estimates<'a, Client: Request>(
 
 /// Get earnings data for a stock
 pub fn earnings<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
 ) -> Earnings<'a, Client, Raw> {
     Earnings::new(client, symbol)
@@ -41,7 +41,7 @@ pub fn earnings<'a, Client: Request>(
 
 /// Get company overview for a stock
 pub fn company_overview<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
 ) -> CompanyOverview<'a, Client, Raw> {
     CompanyOverview::new(client, symbol)
@@ -49,7 +49,7 @@ pub fn company_overview<'a, Client: Request>(
 
 /// Get income statement for a stock
 pub fn income_statement<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
 ) -> IncomeStatement<'a, Client, Raw> {
     IncomeStatement::new(client, symbol)
@@ -57,7 +57,7 @@ pub fn income_statement<'a, Client: Request>(
 
 /// Get balance sheet for a stock
 pub fn balance_sheet<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
 ) -> BalanceSheet<'a, Client, Raw> {
     BalanceSheet::new(client, symbol)
@@ -65,7 +65,7 @@ pub fn balance_sheet<'a, Client: Request>(
 
 /// Get cash flow statement for a stock
 pub fn cash_flow<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
 ) -> CashFlow<'a, Client, Raw> {
     CashFlow::new(client, symbol)
@@ -74,6 +74,7 @@ pub fn cash_flow<'a, Client: Request>(
 #[cfg(all(test, feature = "dotenvy"))]
 mod tests {
     use super::*;
+    use crate::client::AlphaVantage;
 
     fn setup() -> AlphaVantage<reqwest::Client> {
         AlphaVantage::new().expect("Failed to create client. Make sure ALPHAVANTAGE_API_KEY is set in .env file")
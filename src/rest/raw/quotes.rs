@@ -1,14 +1,37 @@
 //! Quote endpoint implementations returning raw JSON strings
 
-use crate::client::AlphaVantage;
+use crate::client::ClientRef;
 use crate::processor::Raw;
 use crate::request::Request;
+use crate::request::global_quote::GlobalQuote;
 use crate::request::realtime_bulk_quotes::RealtimeBulkQuotes;
 
+/// Get the latest quote for a single symbol
+///
+/// Returns a request builder that will return results as raw JSON string.
+///
+/// # Example
+/// ```no_run
+/// # use alphav::AlphaVantage;
+/// # use alphav::execute::Execute;
+/// # async fn example() {
+/// # let client = AlphaVantage::default().with_key("api-key");
+/// let json = alphav::rest::quotes::global_quote(&client, "IBM").get().await.unwrap();
+/// # }
+/// ```
+pub fn global_quote<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> GlobalQuote<'a, Client, Raw> {
+    GlobalQuote::new(client, symbol)
+}
+
 /// Get realtime quotes for up to 100 symbols in a single API call.
 ///
 /// Wraps Alpha Vantage's `REALTIME_BULK_QUOTES` endpoint. Requires a premium
-/// Alpha Vantage subscription.
+/// Alpha Vantage subscription. For larger universes, call
+/// [`chunked`](RealtimeBulkQuotes::chunked) to request the symbols in batches and
+/// merge the results.
 ///
 /// # Example
 /// ```no_run
@@ -23,7 +46,7 @@ use crate::request::realtime_bulk_quotes::RealtimeBulkQuotes;
 /// # }
 /// ```
 pub fn realtime_bulk<'a, Client, I, S>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbols: I,
 ) -> RealtimeBulkQuotes<'a, Client, Raw>
 where
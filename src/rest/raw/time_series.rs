@@ -1,6 +1,6 @@
 //! Time series endpoint implementations returning raw JSON strings
 
-use crate::client::AlphaVantage;
+use crate::client::ClientRef;
 use crate::processor::Raw;
 use crate::request::Request;
 use crate::request::common::Interval;
@@ -24,7 +24,7 @@ use crate::request::time_series::{TimeSeriesDaily, TimeSeriesIntraday, TimeSerie
 /// # }
 /// ```
 pub fn intraday<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
     interval: Interval,
 ) -> TimeSeriesIntraday<'a, Client, Raw> {
@@ -35,7 +35,7 @@ pub fn intraday<'a, Client: Request>(
 ///
 /// Returns a request builder that will return results as raw JSON string.
 pub fn daily<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
 ) -> TimeSeriesDaily<'a, Client, Raw> {
     TimeSeriesDaily::new(client, symbol)
@@ -45,7 +45,7 @@ pub fn daily<'a, Client: Request>(
 ///
 /// Returns a request builder that will return results as raw JSON string.
 pub fn weekly<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
 ) -> TimeSeriesWeekly<'a, Client, Raw> {
     TimeSeriesWeekly::new(client, symbol)
@@ -55,7 +55,7 @@ pub fn weekly<'a, Client: Request>(
 ///
 /// Returns a request builder that will return results as raw JSON string.
 pub fn monthly<'a, Client: Request>(
-    client: &'a AlphaVantage<Client>,
+    client: impl Into<ClientRef<'a, Client>>,
     symbol: impl Into<String>,
 ) -> TimeSeriesMonthly<'a, Client, Raw> {
     TimeSeriesMonthly::new(client, symbol)
@@ -64,6 +64,7 @@ pub fn monthly<'a, Client: Request>(
 #[cfg(all(test, feature = "dotenvy"))]
 mod tests {
     use super::*;
+    use crate::client::AlphaVantage;
 
     fn setup() -> AlphaVantage<reqwest::Client> {
         AlphaVantage::new().expect("Failed to create client. Make sure ALPHAVANTAGE_API_KEY is set in .env file")
@@ -1,2 +1,4 @@
 //! Table REST API endpoints that return Polars DataFrames
-// Note: Table endpoints will be implemented as needed
+pub mod fundamentals;
+pub mod quotes;
+pub mod time_series;
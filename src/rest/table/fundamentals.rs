@@ -0,0 +1,96 @@
+//! Fundamental data endpoint implementations returning Polars DataFrames
+
+use crate::client::ClientRef;
+use crate::processor::TypedTable;
+use crate::request::Request;
+use crate::request::fundamentals::{
+    BalanceSheet, CashFlow, CompanyOverview, Earnings, EarningsEstimates, IncomeStatement,
+};
+
+/// Get analyst earnings estimates for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+///
+/// # Example
+/// ```no_run
+/// # use alphav::AlphaVantage;
+/// # async fn example() {
+/// # let client = AlphaVantage::default().with_key("api-key");
+/// // One row per fiscal period and horizon
+/// let df = alphav::rest::table::fundamentals::earnings_estimates(&client, "AAPL")
+///     .get()
+///     .await
+///     .unwrap();
+/// println!("{df}");
+/// # }
+/// ```
+pub fn earnings_estimates<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> EarningsEstimates<'a, Client, TypedTable> {
+    EarningsEstimates::new(client, symbol).as_dataframe()
+}
+
+/// Get earnings data for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+pub fn earnings<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> Earnings<'a, Client, TypedTable> {
+    Earnings::new(client, symbol).as_dataframe()
+}
+
+/// Get company overview for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+pub fn company_overview<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> CompanyOverview<'a, Client, TypedTable> {
+    CompanyOverview::new(client, symbol).as_dataframe()
+}
+
+/// Get income statements for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+///
+/// # Example
+/// ```no_run
+/// # use alphav::AlphaVantage;
+/// # async fn example() {
+/// # let client = AlphaVantage::default().with_key("api-key");
+/// // One row per annual or quarterly report, tagged by `period_type`
+/// let df = alphav::rest::table::fundamentals::income_statement(&client, "IBM")
+///     .get()
+///     .await
+///     .unwrap();
+/// println!("{df}");
+/// # }
+/// ```
+pub fn income_statement<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> IncomeStatement<'a, Client, TypedTable> {
+    IncomeStatement::new(client, symbol).as_dataframe()
+}
+
+/// Get balance sheets for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+pub fn balance_sheet<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> BalanceSheet<'a, Client, TypedTable> {
+    BalanceSheet::new(client, symbol).as_dataframe()
+}
+
+/// Get cash flow statements for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+pub fn cash_flow<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> CashFlow<'a, Client, TypedTable> {
+    CashFlow::new(client, symbol).as_dataframe()
+}
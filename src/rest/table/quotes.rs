@@ -0,0 +1,43 @@
+//! Quote endpoint implementations returning Polars DataFrames
+
+use crate::client::ClientRef;
+use crate::processor::TypedTable;
+use crate::request::Request;
+use crate::request::global_quote::GlobalQuote;
+use crate::request::realtime_bulk_quotes::RealtimeBulkQuotes;
+
+/// Get the latest quote for a single symbol
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+///
+/// # Example
+/// ```no_run
+/// # use alphav::AlphaVantage;
+/// # async fn example() {
+/// # let client = AlphaVantage::default().with_key("api-key");
+/// let df = alphav::rest::table::quotes::global_quote(&client, "IBM").get().await.unwrap();
+/// println!("{df}");
+/// # }
+/// ```
+pub fn global_quote<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> GlobalQuote<'a, Client, TypedTable> {
+    GlobalQuote::new(client, symbol).as_dataframe()
+}
+
+/// Get realtime quotes for up to 100 symbols in a single API call.
+///
+/// Returns a request builder that will return results as a Polars DataFrame. Requires a
+/// premium Alpha Vantage subscription.
+pub fn realtime_bulk<'a, Client, I, S>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbols: I,
+) -> RealtimeBulkQuotes<'a, Client, TypedTable>
+where
+    Client: Request,
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    RealtimeBulkQuotes::new(client, symbols).as_dataframe()
+}
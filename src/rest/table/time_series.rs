@@ -0,0 +1,63 @@
+//! Time series endpoint implementations returning Polars DataFrames
+
+use crate::client::ClientRef;
+use crate::processor::TypedTable;
+use crate::request::Request;
+use crate::request::common::Interval;
+use crate::request::time_series::{TimeSeriesDaily, TimeSeriesIntraday, TimeSeriesMonthly, TimeSeriesWeekly};
+
+/// Get intraday time series for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+///
+/// # Example
+/// ```no_run
+/// # use alphav::AlphaVantage;
+/// # use alphav::request::common::Interval;
+/// # async fn example() {
+/// # let client = AlphaVantage::default().with_key("api-key");
+/// // One row per bar: timestamp, open, high, low, close, volume
+/// let df = alphav::rest::table::time_series::intraday(&client, "AAPL", Interval::FiveMin)
+///     .get()
+///     .await
+///     .unwrap();
+/// println!("{df}");
+/// # }
+/// ```
+pub fn intraday<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+    interval: Interval,
+) -> TimeSeriesIntraday<'a, Client, TypedTable> {
+    TimeSeriesIntraday::new(client, symbol, interval).as_dataframe()
+}
+
+/// Get daily time series for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+pub fn daily<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> TimeSeriesDaily<'a, Client, TypedTable> {
+    TimeSeriesDaily::new(client, symbol).as_dataframe()
+}
+
+/// Get weekly time series for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+pub fn weekly<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> TimeSeriesWeekly<'a, Client, TypedTable> {
+    TimeSeriesWeekly::new(client, symbol).as_dataframe()
+}
+
+/// Get monthly time series for a stock
+///
+/// Returns a request builder that will return results as a Polars DataFrame.
+pub fn monthly<'a, Client: Request>(
+    client: impl Into<ClientRef<'a, Client>>,
+    symbol: impl Into<String>,
+) -> TimeSeriesMonthly<'a, Client, TypedTable> {
+    TimeSeriesMonthly::new(client, symbol).as_dataframe()
+}
@@ -0,0 +1,122 @@
+//! Secret handling for Alpha Vantage credentials
+//!
+//! [`ApiKey`] wraps the key so it never shows up in `Debug` or `Display`
+//! output and is zeroized when dropped. Use [`ApiKey::expose`] only where the
+//! raw value is actually needed, i.e. when building the request URL.
+//!
+//! Request URLs carry the key in the `apikey` query parameter. Run them
+//! through [`redact_url`] before logging them or putting them in an error.
+
+use std::fmt;
+
+use zeroize::Zeroize;
+
+/// Placeholder printed in place of the API key
+pub const REDACTED: &str = "***";
+
+/// An Alpha Vantage API key
+///
+/// `Debug` and `Display` print [`REDACTED`] instead of the key.
+///
+/// # Examples
+///
+/// ```
+/// use alphav::ApiKey;
+///
+/// let key = ApiKey::new("my_api_key");
+/// assert_eq!(format!("{key:?}"), "ApiKey(***)");
+/// assert_eq!(key.to_string(), "***");
+/// assert_eq!(key.expose(), "my_api_key");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    /// Wrap a raw API key.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    /// The raw key. Avoid logging or formatting the returned value.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ApiKey({REDACTED})")
+    }
+}
+
+impl fmt::Display for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl Drop for ApiKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl From<String> for ApiKey {
+    fn from(key: String) -> Self {
+        Self(key)
+    }
+}
+
+impl From<&str> for ApiKey {
+    fn from(key: &str) -> Self {
+        Self(key.to_string())
+    }
+}
+
+/// Replace the value of the `apikey` query parameter in `url` with [`REDACTED`].
+///
+/// Other parameters and their order are left untouched.
+///
+/// ```
+/// use alphav::secret::redact_url;
+///
+/// assert_eq!(
+///     redact_url("https://www.alphavantage.co/query?function=OVERVIEW&apikey=secret&symbol=IBM"),
+///     "https://www.alphavantage.co/query?function=OVERVIEW&apikey=***&symbol=IBM",
+/// );
+/// ```
+pub fn redact_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let params: Vec<String> = query
+        .split('&')
+        .map(|p| match p.strip_prefix("apikey=") {
+            Some(_) => format!("apikey={REDACTED}"),
+            None => p.to_string(),
+        })
+        .collect();
+    format!("{base}?{}", params.join("&"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "hyper", feature = "reqwest"))]
+    #[test]
+    fn client_debug_does_not_leak_key() {
+        let client = crate::AlphaVantage::default().with_key("hunter2");
+        let debug = format!("{client:?}");
+        assert!(!debug.contains("hunter2"), "got: {debug}");
+        assert!(debug.contains("ApiKey(***)"), "got: {debug}");
+        assert_eq!(client.api_key(), Some("hunter2"));
+    }
+
+    #[test]
+    fn redact_url_without_key_is_unchanged() {
+        let url = "http://127.0.0.1:8080/query?function=OVERVIEW&symbol=IBM";
+        assert_eq!(redact_url(url), url);
+        assert_eq!(redact_url("http://127.0.0.1:8080/"), "http://127.0.0.1:8080/");
+    }
+}
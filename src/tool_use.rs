@@ -7,7 +7,7 @@ use serde_json::{Value, json};
 use crate::client::AlphaVantage;
 use crate::error::{Error, Result};
 use crate::request::Request;
-use crate::request::common::{Interval, OutputSize};
+use crate::request::common::{Interval, OutputSize, Period};
 use crate::rest;
 use crate::rest::fundamentals;
 
@@ -254,6 +254,16 @@ pub fn list_tools() -> Vec<ToolInfo> {
                     "label": {
                         "type": "string",
                         "description": "Human-friendly name for this result (e.g., 'AAPL Income Statement')"
+                    },
+                    "layout": {
+                        "type": "string",
+                        "enum": ["rows", "long", "wide"],
+                        "description": "rows: one row per report (default); long: one row per report and line item; wide: one row per line item, one column per period"
+                    },
+                    "period": {
+                        "type": "string",
+                        "enum": ["annual", "quarterly"],
+                        "description": "Only annual or only quarterly reports, in any layout (wide defaults to annual; rows and long show both)"
                     }
                 },
                 "required": ["symbol"]
@@ -280,6 +290,16 @@ pub fn list_tools() -> Vec<ToolInfo> {
                     "label": {
                         "type": "string",
                         "description": "Human-friendly name for this result (e.g., 'AAPL Balance Sheet')"
+                    },
+                    "layout": {
+                        "type": "string",
+                        "enum": ["rows", "long", "wide"],
+                        "description": "rows: one row per report (default); long: one row per report and line item; wide: one row per line item, one column per period"
+                    },
+                    "period": {
+                        "type": "string",
+                        "enum": ["annual", "quarterly"],
+                        "description": "Only annual or only quarterly reports, in any layout (wide defaults to annual; rows and long show both)"
                     }
                 },
                 "required": ["symbol"]
@@ -306,6 +326,16 @@ pub fn list_tools() -> Vec<ToolInfo> {
                     "label": {
                         "type": "string",
                         "description": "Human-friendly name for this result (e.g., 'AAPL Cash Flow')"
+                    },
+                    "layout": {
+                        "type": "string",
+                        "enum": ["rows", "long", "wide"],
+                        "description": "rows: one row per report (default); long: one row per report and line item; wide: one row per line item, one column per period"
+                    },
+                    "period": {
+                        "type": "string",
+                        "enum": ["annual", "quarterly"],
+                        "description": "Only annual or only quarterly reports, in any layout (wide defaults to annual; rows and long show both)"
                     }
                 },
                 "required": ["symbol"]
@@ -327,22 +357,83 @@ pub fn list_tools() -> Vec<ToolInfo> {
 /// error — those fields carry rate-limit notices, premium-tier prompts, and
 /// invalid-symbol messages that are essential for debugging.
 fn expect_object<'a>(response: &'a Value, key: &str) -> Result<&'a serde_json::Map<String, Value>> {
-    if let Some(obj) = response.get(key).and_then(|v| v.as_object()) {
-        return Ok(obj);
+    match response.get(key).and_then(|v| v.as_object()) {
+        Some(obj) => Ok(obj),
+        None => Err(missing_data(response, key)),
     }
-    let upstream = response
+}
+
+/// Error for a response lacking `key`, carrying Alpha Vantage's own message if it sent one.
+pub(crate) fn missing_data(response: &Value, key: &str) -> Error {
+    match upstream_message(response) {
+        Some(msg) => Error::Custom(format!("Alpha Vantage API: {msg}")),
+        None => Error::Custom(format!("No '{key}' data found in response")),
+    }
+}
+
+/// Alpha Vantage's `Information`, `Note` or `Error Message`, sent in place of data.
+pub(crate) fn upstream_message(response: &Value) -> Option<&str> {
+    response
         .get("Information")
         .or_else(|| response.get("Note"))
         .or_else(|| response.get("Error Message"))
-        .and_then(|v| v.as_str());
-    Err(match upstream {
-        Some(msg) => Error::Custom(format!("Alpha Vantage API: {msg}")),
-        None => Error::Custom(format!("No '{key}' data found in response")),
-    })
+        .and_then(|v| v.as_str())
+}
+
+/// Coerce each row's values to the `dtype` of their column.
+///
+/// `"number"` columns become floats, dropping a trailing `%`, and `"integer"`
+/// columns integers, rounding values such as `"15.0000"`. Values that don't parse
+/// keep their original text. `"None"`, `"-"` and empty strings are `null` in every
+/// column. Dates and datetimes stay ISO 8601 strings.
+pub(crate) fn coerce_rows(mut rows: Vec<Value>, schema: &Schema) -> Value {
+    for row in rows.iter_mut().filter_map(Value::as_object_mut) {
+        for column in schema {
+            if let Some(value) = row.get_mut(&column.name) {
+                *value = coerce(value.take(), &column.dtype);
+            }
+        }
+    }
+    Value::Array(rows)
+}
+
+fn coerce(value: Value, dtype: &str) -> Value {
+    let text = match value {
+        Value::String(text) => text,
+        Value::Number(number) if dtype == "integer" && !number.is_i64() => {
+            return number
+                .as_f64()
+                .and_then(whole)
+                .map_or(Value::Number(number), Value::from);
+        }
+        other => return other,
+    };
+    let trimmed = text.trim();
+    if matches!(trimmed, "None" | "-" | "") {
+        return Value::Null;
+    }
+    let finite = |text: &str| text.parse::<f64>().ok().filter(|number| number.is_finite());
+    let parsed = match dtype {
+        "number" => finite(trimmed.trim_end_matches('%')).map(Value::from),
+        // Counts such as analysts' come as "15.0000"
+        "integer" => trimmed
+            .parse::<i64>()
+            .ok()
+            .or_else(|| finite(trimmed).and_then(whole))
+            .map(Value::from),
+        _ => None,
+    };
+    parsed.unwrap_or(Value::String(text))
+}
+
+/// Round to the nearest `i64`, if in range.
+fn whole(number: f64) -> Option<i64> {
+    let rounded = number.round();
+    (rounded.is_finite() && rounded >= i64::MIN as f64 && rounded < i64::MAX as f64).then_some(rounded as i64)
 }
 
 /// Transform time_series_intraday response (1min, 5min, 15min, 30min, 60min intervals)
-fn intraday_table(response: Value, interval: &str) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn intraday_table(response: Value, interval: &str) -> Result<(Value, Option<Value>, Schema)> {
     let metadata = response.get("Meta Data").cloned();
 
     let time_series_key = format!("Time Series ({})", interval);
@@ -387,7 +478,7 @@ fn intraday_table(response: Value, interval: &str) -> Result<(Value, Option<Valu
         ColumnDef {
             name: "timestamp".to_string(),
             alias: "Timestamp".to_string(),
-            dtype: "string".to_string(),
+            dtype: "datetime".to_string(),
         },
         ColumnDef {
             name: "open".to_string(),
@@ -412,15 +503,15 @@ fn intraday_table(response: Value, interval: &str) -> Result<(Value, Option<Valu
         ColumnDef {
             name: "volume".to_string(),
             alias: "Volume".to_string(),
-            dtype: "number".to_string(),
+            dtype: "integer".to_string(),
         },
     ];
 
-    Ok((json!(data_array), metadata, schema))
+    Ok((coerce_rows(data_array, &schema), metadata, schema))
 }
 
 /// Transform time_series_daily response
-fn daily_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn daily_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
     let metadata = response.get("Meta Data").cloned();
 
     let time_series_obj = expect_object(&response, "Time Series (Daily)")?;
@@ -464,7 +555,7 @@ fn daily_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "date".to_string(),
             alias: "Date".to_string(),
-            dtype: "string".to_string(),
+            dtype: "date".to_string(),
         },
         ColumnDef {
             name: "open".to_string(),
@@ -489,15 +580,15 @@ fn daily_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "volume".to_string(),
             alias: "Volume".to_string(),
-            dtype: "number".to_string(),
+            dtype: "integer".to_string(),
         },
     ];
 
-    Ok((json!(data_array), metadata, schema))
+    Ok((coerce_rows(data_array, &schema), metadata, schema))
 }
 
 /// Transform time_series_weekly response
-fn weekly_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn weekly_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
     let metadata = response.get("Meta Data").cloned();
 
     let time_series_obj = expect_object(&response, "Weekly Time Series")?;
@@ -541,7 +632,7 @@ fn weekly_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "week_ending".to_string(),
             alias: "Week Ending".to_string(),
-            dtype: "string".to_string(),
+            dtype: "date".to_string(),
         },
         ColumnDef {
             name: "open".to_string(),
@@ -566,15 +657,15 @@ fn weekly_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "volume".to_string(),
             alias: "Volume".to_string(),
-            dtype: "number".to_string(),
+            dtype: "integer".to_string(),
         },
     ];
 
-    Ok((json!(data_array), metadata, schema))
+    Ok((coerce_rows(data_array, &schema), metadata, schema))
 }
 
 /// Transform time_series_monthly response
-fn monthly_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn monthly_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
     let metadata = response.get("Meta Data").cloned();
 
     let time_series_obj = expect_object(&response, "Monthly Time Series")?;
@@ -618,7 +709,7 @@ fn monthly_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "month".to_string(),
             alias: "Month".to_string(),
-            dtype: "string".to_string(),
+            dtype: "date".to_string(),
         },
         ColumnDef {
             name: "open".to_string(),
@@ -643,15 +734,15 @@ fn monthly_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "volume".to_string(),
             alias: "Volume".to_string(),
-            dtype: "number".to_string(),
+            dtype: "integer".to_string(),
         },
     ];
 
-    Ok((json!(data_array), metadata, schema))
+    Ok((coerce_rows(data_array, &schema), metadata, schema))
 }
 
 /// Transform company_overview response
-fn overview_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn overview_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
     // Company overview is a single object, so we convert it to a single-row array
     let data_array = vec![response.clone()];
 
@@ -773,11 +864,11 @@ fn overview_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         },
     ];
 
-    Ok((json!(data_array), None, schema))
+    Ok((coerce_rows(data_array, &schema), None, schema))
 }
 
 /// Transform earnings response
-fn earnings_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn earnings_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
     let symbol = response.get("symbol").cloned();
     let metadata = symbol.map(|s| json!({"symbol": s}));
 
@@ -849,7 +940,7 @@ fn earnings_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "fiscal_date_ending".to_string(),
             alias: "Fiscal Date Ending".to_string(),
-            dtype: "string".to_string(),
+            dtype: "date".to_string(),
         },
         ColumnDef {
             name: "reported_eps".to_string(),
@@ -859,7 +950,7 @@ fn earnings_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "reported_date".to_string(),
             alias: "Reported Date".to_string(),
-            dtype: "string".to_string(),
+            dtype: "date".to_string(),
         },
         ColumnDef {
             name: "estimated_eps".to_string(),
@@ -878,11 +969,11 @@ fn earnings_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         },
     ];
 
-    Ok((json!(data_array), metadata, schema))
+    Ok((coerce_rows(data_array, &schema), metadata, schema))
 }
 
 /// Transform earnings_estimates response
-fn estimates_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn estimates_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
     let symbol = response.get("symbol").cloned();
     let metadata = symbol.map(|s| json!({"symbol": s}));
 
@@ -950,7 +1041,7 @@ fn estimates_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "date".to_string(),
             alias: "Date".to_string(),
-            dtype: "string".to_string(),
+            dtype: "date".to_string(),
         },
         ColumnDef {
             name: "horizon".to_string(),
@@ -975,7 +1066,7 @@ fn estimates_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "eps_estimate_analyst_count".to_string(),
             alias: "EPS Analyst Count".to_string(),
-            dtype: "number".to_string(),
+            dtype: "integer".to_string(),
         },
         ColumnDef {
             name: "revenue_estimate_average".to_string(),
@@ -995,15 +1086,15 @@ fn estimates_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "revenue_estimate_analyst_count".to_string(),
             alias: "Revenue Analyst Count".to_string(),
-            dtype: "number".to_string(),
+            dtype: "integer".to_string(),
         },
     ];
 
-    Ok((json!(data_array), metadata, schema))
+    Ok((coerce_rows(data_array, &schema), metadata, schema))
 }
 
 /// Transform income_statement response
-fn income_statement_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn income_statement_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
     let symbol = response.get("symbol").cloned();
     let metadata = symbol.map(|s| json!({"symbol": s}));
 
@@ -1117,7 +1208,7 @@ fn income_statement_table(response: Value) -> Result<(Value, Option<Value>, Sche
         ColumnDef {
             name: "fiscal_date_ending".to_string(),
             alias: "Fiscal Date Ending".to_string(),
-            dtype: "string".to_string(),
+            dtype: "date".to_string(),
         },
         ColumnDef {
             name: "total_revenue".to_string(),
@@ -1166,11 +1257,11 @@ fn income_statement_table(response: Value) -> Result<(Value, Option<Value>, Sche
         },
     ];
 
-    Ok((json!(data_array), metadata, schema))
+    Ok((coerce_rows(data_array, &schema), metadata, schema))
 }
 
 /// Transform balance_sheet response
-fn balance_sheet_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn balance_sheet_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
     let symbol = response.get("symbol").cloned();
     let metadata = symbol.map(|s| json!({"symbol": s}));
 
@@ -1288,7 +1379,7 @@ fn balance_sheet_table(response: Value) -> Result<(Value, Option<Value>, Schema)
         ColumnDef {
             name: "fiscal_date_ending".to_string(),
             alias: "Fiscal Date Ending".to_string(),
-            dtype: "string".to_string(),
+            dtype: "date".to_string(),
         },
         ColumnDef {
             name: "total_assets".to_string(),
@@ -1332,11 +1423,11 @@ fn balance_sheet_table(response: Value) -> Result<(Value, Option<Value>, Schema)
         },
     ];
 
-    Ok((json!(data_array), metadata, schema))
+    Ok((coerce_rows(data_array, &schema), metadata, schema))
 }
 
 /// Transform cash_flow response
-fn cash_flow_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
+pub(crate) fn cash_flow_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
     let symbol = response.get("symbol").cloned();
     let metadata = symbol.map(|s| json!({"symbol": s}));
 
@@ -1504,7 +1595,7 @@ fn cash_flow_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         ColumnDef {
             name: "fiscal_date_ending".to_string(),
             alias: "Fiscal Date Ending".to_string(),
-            dtype: "string".to_string(),
+            dtype: "date".to_string(),
         },
         ColumnDef {
             name: "operating_cashflow".to_string(),
@@ -1568,11 +1659,161 @@ fn cash_flow_table(response: Value) -> Result<(Value, Option<Value>, Schema)> {
         },
     ];
 
-    Ok((json!(data_array), metadata, schema))
+    Ok((coerce_rows(data_array, &schema), metadata, schema))
+}
+
+/// Reshape a statement table to one row per report and line item, with columns
+/// `symbol`, `period_type`, `period`, `line_item` and `value`
+pub(crate) fn long_statement(table: (Value, Option<Value>, Schema)) -> (Value, Option<Value>, Schema) {
+    let (rows, metadata, schema) = table;
+    let symbol = metadata
+        .as_ref()
+        .and_then(|m| m.get("symbol"))
+        .cloned()
+        .unwrap_or(json!(null));
+
+    let mut data_array: Vec<Value> = Vec::new();
+    for row in rows.as_array().into_iter().flatten() {
+        for item in line_items(&schema) {
+            data_array.push(json!({
+                "symbol": symbol,
+                "period_type": row.get("period_type").cloned().unwrap_or(json!(null)),
+                "period": row.get("fiscal_date_ending").cloned().unwrap_or(json!(null)),
+                "line_item": item.name,
+                "value": row.get(&item.name).cloned().unwrap_or(json!(null)),
+            }));
+        }
+    }
+
+    let schema = vec![
+        ColumnDef {
+            name: "symbol".to_string(),
+            alias: "Symbol".to_string(),
+            dtype: "string".to_string(),
+        },
+        ColumnDef {
+            name: "period_type".to_string(),
+            alias: "Period Type".to_string(),
+            dtype: "string".to_string(),
+        },
+        ColumnDef {
+            name: "period".to_string(),
+            alias: "Period".to_string(),
+            dtype: "date".to_string(),
+        },
+        ColumnDef {
+            name: "line_item".to_string(),
+            alias: "Line Item".to_string(),
+            dtype: "string".to_string(),
+        },
+        ColumnDef {
+            name: "value".to_string(),
+            alias: "Value".to_string(),
+            dtype: "number".to_string(),
+        },
+    ];
+
+    (json!(data_array), metadata, schema)
+}
+
+/// Pivot a statement table for presentation: one row per line item, labelled
+/// with its alias (e.g. "Total Revenue"), and one column per `period` report,
+/// named by fiscal date ending, most recent first. Of several reports for the
+/// same fiscal date, the first one in the response is kept.
+pub(crate) fn wide_statement(table: (Value, Option<Value>, Schema), period: Period) -> (Value, Option<Value>, Schema) {
+    let (rows, metadata, schema) = table;
+    let period_type = period.to_string();
+    let mut reports: Vec<(&str, &Value)> = rows
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|row| row.get("period_type").and_then(|v| v.as_str()) == Some(period_type.as_str()))
+        .filter_map(|row| Some((row.get("fiscal_date_ending")?.as_str()?, row)))
+        .collect();
+    // Stable, so duplicates stay in response order for dedup_by_key to keep the first
+    reports.sort_by(|(a, _), (b, _)| b.cmp(a));
+    reports.dedup_by_key(|(period, _)| *period);
+
+    let mut data_array: Vec<Value> = Vec::new();
+    for item in line_items(&schema) {
+        let mut row = serde_json::Map::new();
+        row.insert("line_item".to_string(), json!(item.alias));
+        for (period, report) in &reports {
+            row.insert(
+                period.to_string(),
+                report.get(&item.name).cloned().unwrap_or(json!(null)),
+            );
+        }
+        data_array.push(Value::Object(row));
+    }
+
+    let mut wide_schema = vec![ColumnDef {
+        name: "line_item".to_string(),
+        alias: "Line Item".to_string(),
+        dtype: "string".to_string(),
+    }];
+    wide_schema.extend(reports.iter().map(|(period, _)| ColumnDef {
+        name: period.to_string(),
+        alias: period.to_string(),
+        dtype: "number".to_string(),
+    }));
+
+    (json!(data_array), metadata, wide_schema)
+}
+
+/// A statement table's line-item columns, in schema order
+fn line_items(schema: &Schema) -> impl Iterator<Item = &ColumnDef> {
+    schema.iter().filter(|column| column.dtype == "number")
+}
+
+/// Apply a statement tool's optional `layout` ("rows", "long" or "wide") and
+/// `period` ("annual" or "quarterly") parameters. Without a `period`, the wide
+/// layout shows annual reports and the others show both.
+fn statement_layout(table: (Value, Option<Value>, Schema), params: &Value) -> Result<(Value, Option<Value>, Schema)> {
+    let period: Option<Period> = params
+        .get("period")
+        .and_then(|v| v.as_str())
+        .map(str::parse)
+        .transpose()?;
+    match params.get("layout").and_then(|v| v.as_str()).unwrap_or("rows") {
+        "rows" => Ok(only_period(table, period)),
+        "long" => Ok(long_statement(only_period(table, period))),
+        "wide" => Ok(wide_statement(table, period.unwrap_or(Period::Annual))),
+        layout => Err(Error::Custom(format!("Invalid layout: {layout}"))),
+    }
+}
+
+/// Keep only the statement table's rows for `period`, if given
+fn only_period(table: (Value, Option<Value>, Schema), period: Option<Period>) -> (Value, Option<Value>, Schema) {
+    let (mut rows, metadata, schema) = table;
+    if let (Some(period), Some(reports)) = (period, rows.as_array_mut()) {
+        let period_type = period.to_string();
+        reports.retain(|row| row.get("period_type").and_then(|v| v.as_str()) == Some(period_type.as_str()));
+    }
+    (rows, metadata, schema)
 }
 
 /// Universal tool caller
+///
+/// Table results' metadata carries `fetched_at`, the RFC 3339 time the response
+/// arrived, alongside the response's own metadata.
 pub async fn call_tool<Client: Request>(client: &AlphaVantage<Client>, request: Value) -> Result<ToolResult> {
+    let result = dispatch(client, request).await?;
+    let fetched_at = chrono::DateTime::<chrono::Utc>::from(std::time::SystemTime::now())
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    Ok(match result {
+        ToolResult::DataFrame(mut frame) => {
+            let metadata = frame.metadata.get_or_insert_with(|| json!({}));
+            if let Some(metadata) = metadata.as_object_mut() {
+                metadata.insert("fetched_at".to_string(), json!(fetched_at));
+            }
+            ToolResult::DataFrame(frame)
+        }
+        other => other,
+    })
+}
+
+async fn dispatch<Client: Request>(client: &AlphaVantage<Client>, request: Value) -> Result<ToolResult> {
     let tool = request
         .get("tool")
         .and_then(|v| v.as_str())
@@ -1750,7 +1991,7 @@ pub async fn call_tool<Client: Request>(client: &AlphaVantage<Client>, request:
             let response = query.get().await?;
             let response_json: Value =
                 serde_json::from_str(&response).map_err(|e| Error::Custom(format!("Failed to parse response: {e}")))?;
-            let (data, metadata, schema) = income_statement_table(response_json)?;
+            let (data, metadata, schema) = statement_layout(income_statement_table(response_json)?, params)?;
             let label = ai_label.unwrap_or(symbol);
             Ok(ToolResult::columnar(data, schema, metadata)
                 .with_label(Label::new(label))
@@ -1766,7 +2007,7 @@ pub async fn call_tool<Client: Request>(client: &AlphaVantage<Client>, request:
             let response = query.get().await?;
             let response_json: Value =
                 serde_json::from_str(&response).map_err(|e| Error::Custom(format!("Failed to parse response: {e}")))?;
-            let (data, metadata, schema) = balance_sheet_table(response_json)?;
+            let (data, metadata, schema) = statement_layout(balance_sheet_table(response_json)?, params)?;
             let label = ai_label.unwrap_or(symbol);
             Ok(ToolResult::columnar(data, schema, metadata)
                 .with_label(Label::new(label))
@@ -1782,7 +2023,7 @@ pub async fn call_tool<Client: Request>(client: &AlphaVantage<Client>, request:
             let response = query.get().await?;
             let response_json: Value =
                 serde_json::from_str(&response).map_err(|e| Error::Custom(format!("Failed to parse response: {e}")))?;
-            let (data, metadata, schema) = cash_flow_table(response_json)?;
+            let (data, metadata, schema) = statement_layout(cash_flow_table(response_json)?, params)?;
             let label = ai_label.unwrap_or(symbol);
             Ok(ToolResult::columnar(data, schema, metadata)
                 .with_label(Label::new(label))
@@ -1793,6 +2034,20 @@ pub async fn call_tool<Client: Request>(client: &AlphaVantage<Client>, request:
     }
 }
 
+/// [`call_tool`] bounded by a time limit
+///
+/// Fails with [`Error::Timeout`] if fetching and transforming the data takes
+/// longer than `limit`; the in-flight request is dropped.
+pub async fn call_tool_with_timeout<Client: Request>(
+    client: &AlphaVantage<Client>,
+    request: Value,
+    limit: std::time::Duration,
+) -> Result<ToolResult> {
+    tokio::time::timeout(limit, call_tool(client, request))
+        .await
+        .map_err(|_| Error::Timeout(limit))?
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1830,4 +2085,114 @@ mod tests {
         let obj = expect_object(&body, "Monthly Time Series").unwrap();
         assert!(obj.contains_key("2025-01"));
     }
+
+    #[test]
+    fn coerce_rows_follows_column_dtypes() {
+        let body = json!({
+            "quarterlyEarnings": [{
+                "fiscalDateEnding": "2024-12-31", "reportedDate": "None", "reportedEPS": "3.92",
+                "estimatedEPS": "-", "surprise": "", "surprisePercentage": "3.7"
+            }]
+        });
+        let (rows, _, _) = earnings_table(body).unwrap();
+        assert_eq!(
+            rows[0],
+            json!({
+                "period_type": "quarterly", "fiscal_date_ending": "2024-12-31", "reported_date": null,
+                "reported_eps": 3.92, "estimated_eps": null, "surprise": null, "surprise_percentage": 3.7
+            })
+        );
+
+        let schema = vec![ColumnDef {
+            name: "volume".to_string(),
+            alias: "Volume".to_string(),
+            dtype: "integer".to_string(),
+        }];
+        let rows = coerce_rows(
+            vec![
+                json!({"volume": "3458601"}),
+                json!({"volume": "15.0000"}),
+                json!({"volume": 14.0}),
+                json!({"volume": "lots"}),
+            ],
+            &schema,
+        );
+        assert_eq!(
+            rows,
+            json!([{"volume": 3458601}, {"volume": 15}, {"volume": 14}, {"volume": "lots"}])
+        );
+    }
+
+    #[test]
+    fn statements_reshape_long_and_wide() {
+        let body = json!({
+            "symbol": "IBM",
+            "annualReports": [
+                {"fiscalDateEnding": "2024-12-31", "totalRevenue": "62753000000", "netIncome": "6023000000"},
+                {"fiscalDateEnding": "2023-12-31", "totalRevenue": "61860000000", "netIncome": "None"}
+            ],
+            "quarterlyReports": [{"fiscalDateEnding": "2024-12-31", "totalRevenue": "17553000000"}]
+        });
+        let table = income_statement_table(body).unwrap();
+        let items = line_items(&table.2).count();
+
+        let (rows, _, schema) = long_statement(table.clone());
+        let names: Vec<_> = schema.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["symbol", "period_type", "period", "line_item", "value"]);
+        assert_eq!(rows.as_array().unwrap().len(), 3 * items);
+        assert_eq!(
+            rows[0],
+            json!({"symbol": "IBM", "period_type": "annual", "period": "2024-12-31",
+                   "line_item": "total_revenue", "value": 62753000000.0})
+        );
+
+        let (rows, _, schema) = wide_statement(table.clone(), Period::Annual);
+        let names: Vec<_> = schema.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["line_item", "2024-12-31", "2023-12-31"]);
+        assert_eq!(rows.as_array().unwrap().len(), items);
+        assert_eq!(
+            rows[0],
+            json!({"line_item": "Total Revenue", "2024-12-31": 62753000000.0, "2023-12-31": 61860000000.0})
+        );
+        let net_income = rows
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|row| row["line_item"] == "Net Income")
+            .unwrap();
+        assert_eq!(net_income["2023-12-31"], json!(null));
+
+        let (_, _, schema) = wide_statement(table.clone(), Period::Quarterly);
+        assert_eq!(schema.len(), 2);
+
+        let reports = json!([
+            {"period_type": "annual", "fiscal_date_ending": "2023-12-31", "total_revenue": 1.0},
+            {"period_type": "annual", "fiscal_date_ending": "2024-12-31", "total_revenue": 2.0},
+            {"period_type": "annual", "fiscal_date_ending": "2024-12-31", "total_revenue": 3.0}
+        ]);
+        let (rows, _, schema) = wide_statement((reports, None, table.2.clone()), Period::Annual);
+        let names: Vec<_> = schema.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["line_item", "2024-12-31", "2023-12-31"]);
+        assert_eq!(
+            rows[0],
+            json!({"line_item": "Total Revenue", "2024-12-31": 2.0, "2023-12-31": 1.0})
+        );
+        assert!(statement_layout(table.clone(), &json!({"layout": "sideways"})).is_err());
+        assert!(statement_layout(table.clone(), &json!({"period": "monthly"})).is_err());
+
+        // `period` applies to every layout
+        let (rows, _, _) = statement_layout(table.clone(), &json!({"period": "quarterly"})).unwrap();
+        assert_eq!(rows.as_array().unwrap().len(), 1);
+        assert_eq!(rows[0]["period_type"], "quarterly");
+        let (rows, _, _) = statement_layout(table.clone(), &json!({"layout": "long", "period": "annual"})).unwrap();
+        assert_eq!(rows.as_array().unwrap().len(), 2 * items);
+        assert!(
+            rows.as_array()
+                .unwrap()
+                .iter()
+                .all(|row| row["period_type"] == "annual")
+        );
+        let (rows, _, _) = statement_layout(table, &json!({"layout": "long"})).unwrap();
+        assert_eq!(rows.as_array().unwrap().len(), 3 * items);
+    }
 }
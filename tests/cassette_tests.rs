@@ -0,0 +1,246 @@
+//! Offline tool tests replaying recorded Alpha Vantage responses
+//!
+//! Fixtures live in `tests/fixtures/` and are served by [`Cassette`] in replay
+//! mode, so these tests need neither network access nor an API key. Re-record
+//! them against the live API with:
+//!
+//! ```sh
+//! ALPHAVANTAGE_RECORD=1 ALPHAVANTAGE_API_KEY=... cargo test --test cassette_tests
+//! ```
+
+use alphav::cassette::Cassette;
+use alphav::tool_use::{ToolResult, call_tool};
+use alphav::{AlphaVantage, Request};
+use emporium_core::tool::DataFrame;
+use serde_json::{Value, json};
+
+fn setup() -> alphav::client::AlphaVantage<Cassette<impl Request>> {
+    let key = std::env::var("ALPHAVANTAGE_API_KEY").unwrap_or_else(|_| "replay".to_string());
+    AlphaVantage::default()
+        .with_key(key)
+        .wrap_client(|http| Cassette::wrap(http, "tests/fixtures/tool_use.json"))
+}
+
+/// Replay a tool call that should return a table.
+async fn replay_df(tool: &str, params: Value) -> DataFrame {
+    let result = call_tool(&setup(), json!({ "tool": tool, "params": params }))
+        .await
+        .unwrap_or_else(|e| panic!("{tool} should replay: {e}"));
+    match result {
+        ToolResult::DataFrame(df) => df,
+        other => panic!("expected DataFrame for {tool}, got {other:?}"),
+    }
+}
+
+fn columns(df: &DataFrame) -> Vec<&str> {
+    df.schema.iter().map(|c| c.name.as_str()).collect()
+}
+
+/// Convert to Polars and check the row count and minimum column count.
+fn assert_polars_shape(df: &DataFrame, rows: usize, min_columns: usize) {
+    let polars = df.to_dataframe().expect("rows should convert to a Polars DataFrame");
+    assert_eq!(polars.height(), rows);
+    assert!(
+        polars.width() >= min_columns,
+        "expected at least {min_columns} columns, got {}",
+        polars.width()
+    );
+}
+
+#[tokio::test]
+async fn test_time_series_daily_replay() {
+    let client = setup();
+    let result = call_tool(
+        &client,
+        json!({ "tool": "time_series_daily", "params": { "symbol": "IBM" } }),
+    )
+    .await
+    .expect("time_series_daily should replay");
+
+    let ToolResult::DataFrame(df) = result else {
+        panic!("expected DataFrame, got {result:?}");
+    };
+    let rows = df.data.as_array().expect("rows should be an array");
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0]["date"], "2025-01-08", "rows should be newest first");
+    assert_eq!(rows[0]["close"], 223.18);
+    assert!(df.metadata.is_some());
+    assert_polars_shape(&df, 3, 6);
+}
+
+#[tokio::test]
+async fn test_rate_limit_note_replay() {
+    let client = setup();
+    let err = call_tool(
+        &client,
+        json!({ "tool": "time_series_daily", "params": { "symbol": "MSFT" } }),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("25 requests per day"), "got: {err}");
+}
+
+#[tokio::test]
+async fn test_unrecorded_request_fails() {
+    let client = setup();
+    let err = call_tool(
+        &client,
+        json!({ "tool": "time_series_daily", "params": { "symbol": "AAPL" } }),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("No recorded interaction"), "got: {err}");
+}
+
+#[tokio::test]
+async fn test_time_series_intraday_replay() {
+    let df = replay_df(
+        "time_series_intraday",
+        json!({ "symbol": "IBM", "interval": "5min", "outputsize": "compact" }),
+    )
+    .await;
+
+    let metadata = df.metadata.as_ref().expect("intraday response should carry metadata");
+    assert_eq!(metadata["4. Interval"], "5min");
+    assert_eq!(columns(&df), ["timestamp", "open", "high", "low", "close", "volume"]);
+
+    let rows = df.data.as_array().unwrap();
+    assert_eq!(
+        rows[0]["timestamp"], "2025-01-08 19:55:00",
+        "rows should be newest first"
+    );
+    assert_eq!(rows[0]["open"], 223.18);
+    assert_eq!(rows[0]["volume"], 1204);
+    assert_polars_shape(&df, 3, 6);
+}
+
+#[tokio::test]
+async fn test_time_series_weekly_replay() {
+    let df = replay_df("time_series_weekly", json!({ "symbol": "IBM" })).await;
+    assert!(df.metadata.is_some());
+    assert_eq!(columns(&df)[0], "week_ending");
+    assert_eq!(df.data[0]["week_ending"], "2025-01-08");
+    assert_eq!(df.data[0]["volume"], 8518558);
+    assert_polars_shape(&df, 2, 6);
+}
+
+#[tokio::test]
+async fn test_time_series_monthly_replay() {
+    let df = replay_df("time_series_monthly", json!({ "symbol": "IBM" })).await;
+    assert!(df.metadata.is_some());
+    assert_eq!(columns(&df)[0], "month");
+    assert_eq!(df.data[1]["month"], "2024-12-31");
+    assert_eq!(df.data[1]["close"], 219.83);
+    assert_polars_shape(&df, 2, 6);
+}
+
+#[tokio::test]
+async fn test_company_overview_replay() {
+    let df = replay_df("company_overview", json!({ "symbol": "IBM" })).await;
+    let rows = df.data.as_array().unwrap();
+    assert_eq!(rows.len(), 1, "overview should be a single-row table");
+    assert_eq!(rows[0]["Symbol"], "IBM");
+    assert_eq!(rows[0]["Exchange"], "NYSE");
+    assert_eq!(rows[0]["MarketCapitalization"], 206_356_447_000.0);
+    assert!(rows[0]["PEGRatio"].is_null(), "\"None\" should be null");
+    assert_polars_shape(&df, 1, 20);
+}
+
+#[tokio::test]
+async fn test_earnings_replay() {
+    let df = replay_df("earnings", json!({ "symbol": "IBM" })).await;
+    assert_eq!(df.metadata.as_ref().unwrap()["symbol"], "IBM");
+    let rows = df.data.as_array().unwrap();
+    let quarterly: Vec<&Value> = rows.iter().filter(|r| r["period_type"] == "quarterly").collect();
+    assert_eq!(quarterly.len(), 2);
+    assert_eq!(quarterly[0]["surprise_percentage"], 3.7037);
+    assert_polars_shape(&df, 4, 5);
+}
+
+#[tokio::test]
+async fn test_earnings_estimates_replay() {
+    let df = replay_df("earnings_estimates", json!({ "symbol": "IBM" })).await;
+    assert_eq!(df.metadata.as_ref().unwrap()["symbol"], "IBM");
+    for column in ["date", "horizon", "eps_estimate_average", "revenue_estimate_average"] {
+        assert!(columns(&df).contains(&column), "schema missing column {column}");
+    }
+    assert_eq!(df.data[0]["eps_estimate_average"], 11.36, "rows should be newest first");
+    assert_eq!(
+        df.data[0]["eps_estimate_analyst_count"], 15,
+        "counts such as 15.0000 should be integers"
+    );
+    assert_polars_shape(&df, 2, 8);
+
+    let df = replay_df("earnings_estimates", json!({ "symbol": "IBM", "horizon": "3month" })).await;
+    assert_polars_shape(&df, 1, 8);
+}
+
+#[tokio::test]
+async fn test_income_statement_replay() {
+    let df = replay_df("income_statement", json!({ "symbol": "IBM" })).await;
+    assert_eq!(df.metadata.as_ref().unwrap()["symbol"], "IBM");
+    let rows = df.data.as_array().unwrap();
+    assert_eq!(rows[0]["total_revenue"], 62_753_000_000.0);
+    for row in rows {
+        for column in ["fiscal_date_ending", "gross_profit", "operating_income", "net_income"] {
+            assert!(row.get(column).is_some(), "row missing {column}");
+        }
+    }
+    let quarterly = rows.iter().filter(|r| r["period_type"] == "quarterly").count();
+    assert_eq!(quarterly, 2);
+    assert_polars_shape(&df, 4, 10);
+}
+
+#[tokio::test]
+async fn test_balance_sheet_replay() {
+    let df = replay_df("balance_sheet", json!({ "symbol": "IBM" })).await;
+    assert_eq!(df.metadata.as_ref().unwrap()["symbol"], "IBM");
+    let first = &df.data[0];
+    for column in [
+        "fiscal_date_ending",
+        "total_assets",
+        "total_liabilities",
+        "total_shareholder_equity",
+        "cash_and_cash_equivalents",
+        "common_stock",
+        "retained_earnings",
+    ] {
+        assert!(first.get(column).is_some(), "row missing {column}");
+    }
+    assert_eq!(first["total_assets"], 137_175_000_000.0);
+    assert_polars_shape(&df, 4, 9);
+}
+
+#[tokio::test]
+async fn test_cash_flow_replay() {
+    let df = replay_df("cash_flow", json!({ "symbol": "IBM" })).await;
+    assert_eq!(df.metadata.as_ref().unwrap()["symbol"], "IBM");
+    let first = &df.data[0];
+    for column in [
+        "fiscal_date_ending",
+        "operating_cashflow",
+        "capital_expenditures",
+        "cashflow_from_investment",
+        "cashflow_from_financing",
+    ] {
+        assert!(first.get(column).is_some(), "row missing {column}");
+    }
+    assert_eq!(first["operating_cashflow"], 13_445_000_000.0);
+    assert_polars_shape(&df, 4, 12);
+}
+
+#[tokio::test]
+async fn test_invalid_params_fail_before_replay() {
+    let client = setup();
+    for request in [
+        json!({ "tool": "invalid_tool_name", "params": { "symbol": "IBM" } }),
+        json!({ "tool": "time_series_daily", "params": {} }),
+        json!({ "tool": "time_series_intraday", "params": { "symbol": "IBM" } }),
+        json!({ "tool": "time_series_intraday", "params": { "symbol": "IBM", "interval": "2min" } }),
+    ] {
+        assert!(
+            call_tool(&client, request.clone()).await.is_err(),
+            "{request} should fail"
+        );
+    }
+}
@@ -1,16 +1,26 @@
 //! DataFrame conversion tests for all Alpha Vantage endpoints
-use alphav::AlphaVantage;
+//!
+//! Responses are replayed from `tests/fixtures/dataframe_tests.json`. Re-record
+//! them against the live API with:
+//!
+//! ```sh
+//! ALPHAVANTAGE_RECORD=1 ALPHAVANTAGE_API_KEY=... cargo test --test dataframe_tests --features table
+//! ```
+use alphav::cassette::Cassette;
 use alphav::tool_use::{ToolResult, call_tool};
+use alphav::{AlphaVantage, Request};
 use serde_json::json;
 use std::env;
 
-fn setup() -> Result<AlphaVantage, Box<dyn std::error::Error>> {
-    let api_key = env::var("ALPHAVANTAGE_API_KEY").map_err(|_| "ALPHAVANTAGE_API_KEY environment variable not set")?;
-    Ok(AlphaVantage::default().with_key(api_key))
+fn setup() -> Result<alphav::client::AlphaVantage<Cassette<impl Request>>, Box<dyn std::error::Error>> {
+    let api_key = env::var("ALPHAVANTAGE_API_KEY").unwrap_or_else(|_| "replay".to_string());
+    Ok(AlphaVantage::default()
+        .with_key(api_key)
+        .wrap_client(|http| Cassette::wrap(http, "tests/fixtures/dataframe_tests.json")))
 }
 
 async fn check_endpoint(
-    client: &AlphaVantage,
+    client: &alphav::client::AlphaVantage<impl Request>,
     tool_name: &str,
     params: serde_json::Value,
     min_rows: usize,
@@ -57,7 +67,6 @@ async fn check_endpoint(
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_time_series_intraday_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -77,7 +86,6 @@ async fn test_time_series_intraday_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_time_series_daily_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -96,7 +104,6 @@ async fn test_time_series_daily_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_time_series_weekly_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -112,7 +119,6 @@ async fn test_time_series_weekly_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_time_series_monthly_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -128,7 +134,6 @@ async fn test_time_series_monthly_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_company_overview_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -144,7 +149,6 @@ async fn test_company_overview_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_earnings_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -160,7 +164,6 @@ async fn test_earnings_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_earnings_estimates_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -176,7 +179,6 @@ async fn test_earnings_estimates_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_earnings_estimates_with_horizon_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -195,7 +197,6 @@ async fn test_earnings_estimates_with_horizon_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_income_statement_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -211,7 +212,6 @@ async fn test_income_statement_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_balance_sheet_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -227,7 +227,6 @@ async fn test_balance_sheet_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_cash_flow_dataframe() {
     let client = setup().expect("Failed to initialize client");
 
@@ -243,7 +242,6 @@ async fn test_cash_flow_dataframe() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_all_endpoints_return_valid_dataframes() {
     let client = setup().expect("Failed to initialize client");
 
@@ -278,3 +276,101 @@ async fn test_all_endpoints_return_valid_dataframes() {
 
     println!("🎉 All 10 endpoints returned valid DataFrames!");
 }
+
+#[cfg(feature = "table")]
+mod builders {
+    //! The same fixtures read through the request builders' `as_dataframe()`
+    use super::setup;
+    use alphav::request::common::{Interval, OutputSize};
+    use alphav::rest;
+    use polars_core::prelude::DataType;
+
+    #[tokio::test]
+    async fn test_time_series_builders_as_dataframe() {
+        let client = setup().expect("Failed to initialize client");
+
+        let intraday = rest::time_series::intraday(&client, "AAPL", Interval::FiveMin)
+            .outputsize(OutputSize::Compact)
+            .as_dataframe()
+            .get()
+            .await
+            .expect("intraday should replay as a DataFrame");
+        assert_eq!(intraday.shape(), (100, 6));
+        assert!(matches!(
+            intraday.column("timestamp").unwrap().dtype(),
+            DataType::Datetime(..)
+        ));
+
+        let daily = rest::time_series::daily(&client, "AAPL")
+            .outputsize(OutputSize::Compact)
+            .as_dataframe()
+            .get()
+            .await
+            .expect("daily should replay as a DataFrame");
+        assert_eq!(daily.shape(), (100, 6));
+        assert_eq!(daily.column("date").unwrap().dtype(), &DataType::Date);
+        assert_eq!(daily.column("close").unwrap().dtype(), &DataType::Float64);
+
+        let weekly = rest::time_series::weekly(&client, "AAPL")
+            .as_dataframe()
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(weekly.shape(), (120, 6));
+        let monthly = rest::time_series::monthly(&client, "AAPL")
+            .as_dataframe()
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(monthly.shape(), (60, 6));
+    }
+
+    #[tokio::test]
+    async fn test_fundamentals_builders_as_dataframe() {
+        let client = setup().expect("Failed to initialize client");
+
+        let overview = rest::fundamentals::company_overview(&client, "AAPL")
+            .as_dataframe()
+            .get()
+            .await
+            .expect("overview should replay as a DataFrame");
+        assert_eq!(overview.height(), 1);
+        assert!(overview.width() >= 20);
+
+        let earnings = rest::fundamentals::earnings(&client, "AAPL")
+            .as_dataframe()
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(earnings.height(), 14, "6 annual + 8 quarterly rows");
+
+        let estimates = rest::fundamentals::earnings_estimates(&client, "AAPL")
+            .horizon("3month")
+            .as_dataframe()
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(estimates.height(), 1);
+
+        for df in [
+            rest::fundamentals::income_statement(&client, "AAPL")
+                .as_dataframe()
+                .get()
+                .await
+                .unwrap(),
+            rest::fundamentals::balance_sheet(&client, "AAPL")
+                .as_dataframe()
+                .get()
+                .await
+                .unwrap(),
+            rest::fundamentals::cash_flow(&client, "AAPL")
+                .as_dataframe()
+                .get()
+                .await
+                .unwrap(),
+        ] {
+            assert_eq!(df.height(), 25, "5 annual + 20 quarterly rows");
+            assert_eq!(df.column("fiscal_date_ending").unwrap().dtype(), &DataType::Date);
+        }
+    }
+}
@@ -2,23 +2,24 @@
 //!
 //! These tests verify that each tool can be called successfully and returns a
 //! `ToolResult::DataFrame` whose schema and data match the transformed shape
-//! produced by the module. They require a valid `ALPHAVANTAGE_API_KEY` and are
-//! marked `#[ignore]` to avoid burning API quota.
+//! produced by the module. Responses are replayed from
+//! `tests/fixtures/tool_use_tests.json`; re-record them against the live API with:
+//!
+//! ```sh
+//! ALPHAVANTAGE_RECORD=1 ALPHAVANTAGE_API_KEY=... cargo test --test tool_use_tests
+//! ```
 
-use alphav::AlphaVantage;
+use alphav::cassette::Cassette;
 use alphav::tool_use::{ToolResult, call_tool, get_tool_details, list_tools};
+use alphav::{AlphaVantage, Request};
 use emporium_core::tool::DataFrame;
 use serde_json::{Value, json};
 
-#[cfg(feature = "dotenvy")]
-fn setup_client() -> AlphaVantage {
-    AlphaVantage::new().expect("Failed to create client. Make sure ALPHAVANTAGE_API_KEY is set")
-}
-
-#[cfg(not(feature = "dotenvy"))]
-fn setup_client() -> AlphaVantage {
-    let api_key = std::env::var("ALPHAVANTAGE_API_KEY").expect("ALPHAVANTAGE_API_KEY must be set");
-    AlphaVantage::default().with_key(api_key)
+fn setup_client() -> alphav::client::AlphaVantage<Cassette<impl Request>> {
+    let api_key = std::env::var("ALPHAVANTAGE_API_KEY").unwrap_or_else(|_| "replay".to_string());
+    AlphaVantage::default()
+        .with_key(api_key)
+        .wrap_client(|http| Cassette::wrap(http, "tests/fixtures/tool_use_tests.json"))
 }
 
 /// Pattern-unwrap a successful `ToolResult` into the inner `DataFrame` (panicking
@@ -38,7 +39,6 @@ fn expect_rows(df: &DataFrame) -> &[Value] {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_list_tools() {
     let tools = list_tools();
     assert_eq!(tools.len(), 10, "Expected 10 tools");
@@ -61,7 +61,6 @@ async fn test_list_tools() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_get_tool_details() {
     let tool = get_tool_details("time_series_daily").expect("time_series_daily should exist");
     assert_eq!(tool.id, "time_series_daily");
@@ -70,7 +69,6 @@ async fn test_get_tool_details() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_time_series_intraday() {
     let client = setup_client();
     let request = json!({
@@ -101,12 +99,13 @@ async fn test_time_series_intraday() {
     let first = &rows[0];
     let ts = first.get("timestamp").and_then(|v| v.as_str()).expect("timestamp");
     assert!(ts.len() >= 19 && ts.contains(' '), "unexpected timestamp format: {ts}");
-    let open = first.get("open").and_then(|v| v.as_str()).expect("open");
-    open.parse::<f64>().expect("open should be numeric");
+    first
+        .get("open")
+        .and_then(|v| v.as_f64())
+        .expect("open should be numeric");
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_time_series_daily() {
     let client = setup_client();
     let request = json!({
@@ -132,15 +131,11 @@ async fn test_time_series_daily() {
             date.len() == 10 && date.chars().nth(4) == Some('-'),
             "date should be YYYY-MM-DD: {date}"
         );
-        let open: f64 = row.get("open").and_then(|v| v.as_str()).unwrap().parse().unwrap();
-        let high: f64 = row.get("high").and_then(|v| v.as_str()).unwrap().parse().unwrap();
-        let low: f64 = row.get("low").and_then(|v| v.as_str()).unwrap().parse().unwrap();
-        let close: f64 = row.get("close").and_then(|v| v.as_str()).unwrap().parse().unwrap();
-        row.get("volume")
-            .and_then(|v| v.as_str())
-            .unwrap()
-            .parse::<u64>()
-            .expect("volume numeric");
+        let open = row.get("open").and_then(|v| v.as_f64()).unwrap();
+        let high = row.get("high").and_then(|v| v.as_f64()).unwrap();
+        let low = row.get("low").and_then(|v| v.as_f64()).unwrap();
+        let close = row.get("close").and_then(|v| v.as_f64()).unwrap();
+        row.get("volume").and_then(|v| v.as_u64()).expect("volume numeric");
 
         assert!(high >= low, "high should be >= low");
         assert!(open >= low && open <= high, "open within [low, high]");
@@ -149,7 +144,6 @@ async fn test_time_series_daily() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_time_series_weekly() {
     let client = setup_client();
     let request = json!({
@@ -169,7 +163,6 @@ async fn test_time_series_weekly() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_time_series_monthly() {
     let client = setup_client();
     let request = json!({
@@ -184,7 +177,6 @@ async fn test_time_series_monthly() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_company_overview() {
     let client = setup_client();
     let request = json!({
@@ -207,11 +199,9 @@ async fn test_company_overview() {
     );
     assert_eq!(row.get("Exchange").and_then(|v| v.as_str()), Some("NASDAQ"));
 
-    let market_cap = row
-        .get("MarketCapitalization")
-        .and_then(|v| v.as_str())
-        .expect("MarketCapitalization");
-    market_cap.parse::<u64>().expect("market cap numeric");
+    row.get("MarketCapitalization")
+        .and_then(|v| v.as_f64())
+        .expect("market cap numeric");
 
     assert!(row.get("PERatio").is_some());
     assert!(row.get("DividendYield").is_some());
@@ -226,7 +216,6 @@ async fn test_company_overview() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_earnings() {
     let client = setup_client();
     let request = json!({
@@ -249,7 +238,6 @@ async fn test_earnings() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_earnings_estimates() {
     let client = setup_client();
     let request = json!({
@@ -264,7 +252,6 @@ async fn test_earnings_estimates() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_income_statement() {
     let client = setup_client();
     let request = json!({
@@ -285,11 +272,10 @@ async fn test_income_statement() {
     assert!(!rows.is_empty());
     let first = &rows[0];
     assert!(first.get("fiscal_date_ending").is_some());
-    let revenue = first
+    first
         .get("total_revenue")
-        .and_then(|v| v.as_str())
-        .expect("total_revenue");
-    revenue.parse::<i64>().expect("revenue numeric");
+        .and_then(|v| v.as_f64())
+        .expect("revenue numeric");
     assert!(first.get("gross_profit").is_some());
     assert!(first.get("operating_income").is_some());
     assert!(first.get("net_income").is_some());
@@ -306,7 +292,6 @@ async fn test_income_statement() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_balance_sheet() {
     let client = setup_client();
     let request = json!({
@@ -335,7 +320,6 @@ async fn test_balance_sheet() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_cash_flow() {
     let client = setup_client();
     let request = json!({
@@ -362,7 +346,6 @@ async fn test_cash_flow() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_invalid_tool() {
     let client = setup_client();
     let request = json!({ "tool": "invalid_tool_name", "params": { "symbol": "AAPL" } });
@@ -370,7 +353,6 @@ async fn test_invalid_tool() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_missing_required_params() {
     let client = setup_client();
 
@@ -388,7 +370,6 @@ async fn test_missing_required_params() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_invalid_interval() {
     let client = setup_client();
     let request = json!({
@@ -399,7 +380,6 @@ async fn test_invalid_interval() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_optional_params() {
     let client = setup_client();
 